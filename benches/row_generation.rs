@@ -0,0 +1,122 @@
+//! Throughput of the row-generation path, the senders' hottest loop, without a database
+
+use std::sync::Mutex;
+
+use criterion::{Criterion, Throughput, criterion_group, criterion_main};
+use qdb_blaster::{
+    blasting::{RowGenerator, RowSource},
+    pool,
+    settings::Table,
+};
+use serde_json::json;
+
+/// Rows generated per batch, a typical batch size
+const BATCH_ROWS: u32 = 10_000;
+
+/// Rows generated per iteration of the wide table, in batches of `BATCH_ROWS`
+const WIDE_ROWS: u32 = 10_000_000;
+
+/// Tables of a large config, generated on the pool `blast_tables` runs them on
+const TABLES: usize = 64;
+const TABLE_ROWS: u32 = 1_000;
+
+/// A metrics table like the README's, generating rows with a seed so runs compare
+fn metrics_table() -> Table {
+    serde_json::from_value(json!({
+        "schema": [
+            ["ts", "Timestamp"],
+            ["host", "Symbol"],
+            ["region", "Symbol"],
+            ["cpu_usage", "Double"],
+            ["mem_used", "Long"],
+            ["status", "Varchar"],
+        ],
+        "designated_ts": "ts",
+        "seed": 42,
+        "send": {
+            "batch_pause": ["0s", "0s"],
+            "batch_size": [BATCH_ROWS, BATCH_ROWS],
+            "parallel_senders": 1,
+            "batches_connection_keepalive": 1,
+        },
+    }))
+    .unwrap()
+}
+
+/// 20 columns, so per-cell costs such as wrapping the column names dominate
+fn wide_table() -> Table {
+    let mut schema = vec![json!(["ts", "Timestamp"])];
+    schema.extend((0..4).map(|i| json!([format!("sym_{}", i), "Symbol"])));
+    schema.extend((0..10).map(|i| json!([format!("double_{}", i), "Double"])));
+    schema.extend((0..5).map(|i| json!([format!("long_{}", i), "Long"])));
+    let mut table = metrics_table();
+    table.schema = serde_json::from_value(json!(schema)).unwrap();
+    table
+}
+
+fn row_generation(c: &mut Criterion) {
+    let source = RowSource::new("metrics", &metrics_table()).unwrap();
+    let mut generator = RowGenerator::new(&source).unwrap();
+    let mut group = c.benchmark_group("row_generation");
+    group.throughput(Throughput::Elements(BATCH_ROWS.into()));
+    group.bench_function("metrics", |b| {
+        b.iter(|| generator.generate(BATCH_ROWS).unwrap().len())
+    });
+    group.finish();
+}
+
+/// 10M rows of 20 columns, with the row context built once per sender against the baseline
+/// of rebuilding it for every batch
+fn wide_rows(c: &mut Criterion) {
+    let source = RowSource::new("wide", &wide_table()).unwrap();
+    let mut group = c.benchmark_group("wide_20_columns");
+    group.sample_size(10);
+    group.throughput(Throughput::Elements(WIDE_ROWS.into()));
+    for per_batch_context in [true, false] {
+        let name = if per_batch_context {
+            "per_batch_context"
+        } else {
+            "per_sender_context"
+        };
+        let mut generator = RowGenerator::new(&source).unwrap();
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                for _ in 0..WIDE_ROWS / BATCH_ROWS {
+                    if per_batch_context {
+                        generator.rebuild_context().unwrap();
+                    }
+                    generator.generate(BATCH_ROWS).unwrap();
+                }
+            })
+        });
+    }
+    group.finish();
+}
+
+/// Many tables on the table pool with a worker each, as before `max_concurrent_tables`
+/// had a default, against the default of a worker per core
+fn table_workers(c: &mut Criterion) {
+    // Generators borrow their source, which can't be shared between threads on its own
+    let sources: Vec<Mutex<RowSource>> = (0..TABLES)
+        .map(|i| Mutex::new(RowSource::new(&format!("metrics_{}", i), &metrics_table()).unwrap()))
+        .collect();
+    let generate = |source: &Mutex<RowSource>| {
+        let source = source.lock().unwrap();
+        let mut generator = RowGenerator::new(&source).unwrap();
+        generator.generate(TABLE_ROWS).unwrap();
+    };
+    let mut group = c.benchmark_group("table_workers");
+    group.throughput(Throughput::Elements(
+        (TABLES as u64) * u64::from(TABLE_ROWS),
+    ));
+    group.bench_function("worker_per_table", |b| {
+        b.iter(|| pool::run("table-worker", &sources, TABLES, generate).unwrap())
+    });
+    group.bench_function("default_workers", |b| {
+        b.iter(|| pool::run("table-worker", &sources, pool::default_workers(), generate).unwrap())
+    });
+    group.finish();
+}
+
+criterion_group!(benches, row_generation, wide_rows, table_workers);
+criterion_main!(benches);
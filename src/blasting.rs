@@ -1,41 +1,229 @@
 use std::{
+    any::Any,
+    borrow::Cow,
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+    fmt::{self, Write},
+    fs,
+    io::{BufRead, BufReader},
+    iter,
+    net::{Ipv4Addr, TcpStream, ToSocketAddrs},
+    panic::{self, AssertUnwindSafe},
     sync::{
-        Arc,
-        atomic::{AtomicU64, Ordering},
+        Arc, OnceLock,
+        atomic::{AtomicBool, AtomicU16, AtomicU64, Ordering},
     },
     thread,
-    time::{Duration, SystemTime, UNIX_EPOCH},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
 };
 
 use anyhow::{Context, Result};
-use chrono::{DateTime, Utc};
-use postgres::{Client, NoTls};
-use questdb::ingress::{Buffer, ColumnName, Sender as QuestDbSender, TableName, TimestampNanos};
-use rand::{Rng, rngs::ThreadRng};
-use tracing::{debug, error, info, warn};
+use chrono::{DateTime, Datelike, Timelike, Utc, Weekday};
+use hdrhistogram::Histogram;
+use indexmap::{IndexMap, IndexSet};
+use native_tls::{Certificate, TlsConnector};
+use postgres::{Client, NoTls, SimpleQueryMessage};
+use postgres_native_tls::MakeTlsConnector;
+use questdb::{
+    ErrorCode,
+    ingress::{
+        Buffer, ColumnName, ProtocolVersion, Sender as QuestDbSender, TableName, Timestamp,
+        TimestampMicros, TimestampNanos,
+    },
+};
+use rand::{
+    Rng, SeedableRng,
+    distr::{Alphanumeric, Distribution, Uniform, weighted::WeightedIndex},
+    rngs::StdRng,
+};
+use rand_distr::{Exp1, Normal, Zipf};
+use tracing::{debug, error, info, info_span, warn};
 
 use crate::{
+    checkpoint::{Checkpoint, CheckpointWriter, SenderProgress},
     col::ColType,
-    settings::{Connection, SendSettings, Table},
+    estimate,
+    hook::{FlushContext, NoopHook, PreFlushHook},
+    latency::{self, LatencyCollector, LatencySummary},
+    metrics::{Metrics, TableMetrics},
+    pool,
+    ratelimit::RateLimiter,
+    report::TableReport,
+    semaphore::{Semaphore, SemaphorePermit},
+    settings::{
+        self, ColumnSettings, ColumnValues, Connection, Derivation, Generator, HttpSettings,
+        IlpProtocol, Ipv4Cidr, PartitionBy, PauseDistribution, SendSettings, SymbolTemplate, Table,
+        TableMode, TsMode, TsPrecision, ValueDistribution,
+    },
+    stats::{ColumnStats, StatsCollector, ValueStats},
+    symbols::SymbolFile,
+    trace::LatencyTrace,
+    webhook::{self, TableSummary},
 };
 
-/// Pre-generated pool of symbol values to randomly select from
+/// Default number of pre-generated symbol values to randomly select from
 const SYMBOL_POOL_SIZE: usize = 4000;
 
-/// Data generator for creating synthetic values for different column types
+/// Length range, in characters, of symbols generated from a custom alphabet
+const ALPHABET_SYMBOL_LEN: (usize, usize) = (4, 12);
+
+/// Length range, in characters, of generated varchar values
+pub const VARCHAR_LEN: (usize, usize) = (4, 64);
+
+/// Element count range of DoubleArray values without a configured `array_len`
+pub const DEFAULT_ARRAY_LEN: (usize, usize) = (1, 16);
+
+/// Random stream of the symbol pools, distinct from every sender's stream
+const SYMBOL_POOL_STREAM: u64 = u64::MAX;
+
+fn now_nanos() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_nanos() as i64
+}
+
+/// Converts a generated timestamp, in nanoseconds, to the table's ILP precision
+fn ilp_timestamp(precision: TsPrecision, nanos: i64) -> Timestamp {
+    match precision {
+        TsPrecision::Micros => TimestampMicros::new(nanos.div_euclid(1_000)).into(),
+        TsPrecision::Nanos => TimestampNanos::new(nanos).into(),
+    }
+}
+
+/// Start, in nanoseconds, of the partition holding `ts`, or the earliest timestamp when the
+/// table is unpartitioned
+fn partition_start(partition_by: PartitionBy, ts: i64) -> i64 {
+    let time = DateTime::from_timestamp_nanos(ts).naive_utc();
+    let date = time.date();
+    let start = match partition_by {
+        PartitionBy::None => return i64::MIN,
+        PartitionBy::Hour => date.and_hms_opt(time.hour(), 0, 0),
+        PartitionBy::Day => date.and_hms_opt(0, 0, 0),
+        PartitionBy::Week => date.week(Weekday::Mon).first_day().and_hms_opt(0, 0, 0),
+        PartitionBy::Month => date.with_day(1).and_then(|day| day.and_hms_opt(0, 0, 0)),
+        PartitionBy::Year => date
+            .with_ordinal(1)
+            .and_then(|day| day.and_hms_opt(0, 0, 0)),
+    };
+    start
+        .and_then(|start| start.and_utc().timestamp_nanos_opt())
+        .unwrap_or(i64::MIN)
+}
+
+/// RNG for one stream of a table: derived deterministically from the table's seed when
+/// set, so each sender is reproducible on its own, and seeded from the OS otherwise
+fn seeded_rng(seed: Option<u64>, stream: u64) -> StdRng {
+    match seed {
+        Some(seed) => StdRng::seed_from_u64(seed ^ stream.wrapping_mul(0x9E37_79B9_7F4A_7C15)),
+        None => StdRng::from_os_rng(),
+    }
+}
+
+/// Values a symbol column picks from, shared by all senders of a table
 #[derive(Debug)]
-struct DataGenerator {
-    symbols: Vec<String>,
-    base_timestamp: DateTime<Utc>,
-    rng: ThreadRng,
+struct SymbolPool {
+    values: Vec<String>,
+    /// Sampling weights when loaded from a weighted file, `None` means uniform sampling
+    weights: Option<WeightedIndex<f64>>,
 }
 
-impl DataGenerator {
-    fn new(base_timestamp: DateTime<Utc>) -> Self {
-        let rng = rand::rng();
+impl SymbolPool {
+    /// Builds the pool for one symbol column. Without a cardinality the pool keeps
+    /// `pool_size` values of the default mix, with one it holds that many distinct values.
+    /// A template takes precedence over the table's alphabet, whose values are all distinct.
+    fn new(
+        col_name: &str,
+        cardinality: Option<usize>,
+        pool_size: usize,
+        template: Option<&SymbolTemplate>,
+        symbol_alphabet: Option<&[char]>,
+        symbol_file: Option<&SymbolFile>,
+        rng: &mut StdRng,
+    ) -> Result<Self> {
+        let pool_size = cardinality.unwrap_or(pool_size);
+
+        let values: Vec<String> = match (symbol_file, template, symbol_alphabet) {
+            (Some(file), _, _) => file.values.clone(),
+            (None, Some(template), _) => (0..pool_size).map(|i| template.render(i)).collect(),
+            (None, None, Some(alphabet)) => {
+                let distinct = alphabet_symbol_count(alphabet.len());
+                if let Some(cardinality) = cardinality
+                    && cardinality > distinct
+                {
+                    return Err(anyhow::anyhow!(
+                        "Symbol column '{}' has a cardinality of {}, but its alphabet of {} \
+                         characters only makes {} distinct values",
+                        col_name,
+                        cardinality,
+                        alphabet.len(),
+                        distinct
+                    ));
+                }
+                // Drawn in order, so a seed reproduces the same pool
+                let pool_size = pool_size.min(distinct);
+                let mut values = IndexSet::with_capacity(pool_size);
+                while values.len() < pool_size {
+                    let len = rng.random_range(ALPHABET_SYMBOL_LEN.0..=ALPHABET_SYMBOL_LEN.1);
+                    values.insert(
+                        (0..len)
+                            .map(|_| alphabet[rng.random_range(0..alphabet.len())])
+                            .collect::<String>(),
+                    );
+                }
+                values.into_iter().collect()
+            }
+            (None, None, None) => match cardinality {
+                Some(cardinality) => (0..cardinality)
+                    .map(|i| format!("{}-{}", col_name, i))
+                    .collect(),
+                None => Self::template_symbols(pool_size),
+            },
+        };
+        let weights = symbol_file.and_then(|file| file.weights.clone());
+
+        Ok(Self { values, weights })
+    }
+
+    fn from_file(file: SymbolFile) -> Self {
+        Self {
+            values: file.values,
+            weights: file.weights,
+        }
+    }
+
+    fn from_values(values: &ColumnValues) -> Result<Self> {
+        let (values, weights) = match values {
+            ColumnValues::Uniform(values) => (values.clone(), None),
+            ColumnValues::Weighted(weighted) => {
+                let weights = WeightedIndex::new(weighted.values())
+                    .map_err(|e| anyhow::anyhow!("Invalid value weights: {}", e))?;
+                (weighted.keys().cloned().collect(), Some(weights))
+            }
+        };
+        if values.is_empty() {
+            return Err(anyhow::anyhow!("At least one value is required"));
+        }
+        if let Some(value) = values
+            .iter()
+            .find(|value| value.is_empty() || value.chars().any(char::is_control))
+        {
+            return Err(anyhow::anyhow!("Invalid value {:?}", value));
+        }
+        Ok(Self { values, weights })
+    }
 
-        // Pre-generate symbol pool
-        let symbols: Vec<String> = (0..SYMBOL_POOL_SIZE)
+    /// Picks the index of a value, honouring the weights if any
+    fn sample(&self, rng: &mut StdRng) -> usize {
+        match &self.weights {
+            Some(weights) => weights.sample(rng),
+            // Never empty: cardinalities are at least 1 and value files need a value
+            None => rng.random_range(0..self.values.len()),
+        }
+    }
+
+    fn template_symbols(pool_size: usize) -> Vec<String> {
+        (0..pool_size)
             .map(|i| {
                 let variants = [
                     format!("host-{:04}", i % 100),
@@ -49,29 +237,222 @@ impl DataGenerator {
                 ];
                 variants[i % variants.len()].clone()
             })
-            .collect();
+            .collect()
+    }
+}
+
+/// Data generator for creating synthetic values for different column types
+#[derive(Debug)]
+struct DataGenerator {
+    /// One pool per symbol column, in symbol column order
+    symbol_pools: Vec<Arc<SymbolPool>>,
+    base_timestamp: DateTime<Utc>,
+    rng: StdRng,
+    /// Last sequence value per field column and symbol value. Keyed by value rather than
+    /// pool index, since pools can hold a value more than once.
+    sequences: HashMap<usize, HashMap<String, i64>>,
+    /// Next value of each sequence column, by field column
+    counters: HashMap<usize, i64>,
+    /// Correlation id of the event currently being generated and the rows it still needs
+    event_id: String,
+    event_rows_left: u32,
+    events_started: u64,
+    /// Reused across rows to avoid allocating every varchar and array value
+    string_buf: String,
+    array_buf: Vec<f64>,
+    /// Per field column statistics when `column_stats` is enabled
+    stats: Option<Vec<ValueStats>>,
+}
 
+impl DataGenerator {
+    fn new(base_timestamp: DateTime<Utc>, symbol_pools: Vec<Arc<SymbolPool>>, rng: StdRng) -> Self {
         Self {
-            symbols,
+            symbol_pools,
             base_timestamp,
             rng,
+            sequences: HashMap::new(),
+            counters: HashMap::new(),
+            event_id: String::new(),
+            event_rows_left: 0,
+            events_started: 0,
+            string_buf: String::new(),
+            array_buf: Vec::new(),
+            stats: None,
+        }
+    }
+
+    fn record(&mut self, field_idx: usize, value: f64) {
+        if let Some(stats) = &mut self.stats {
+            stats[field_idx].record(value);
+        }
+    }
+
+    fn record_null(&mut self, field_idx: usize) {
+        if let Some(stats) = &mut self.stats {
+            stats[field_idx].record_null();
+        }
+    }
+
+    /// Records every element of the last generated array
+    fn record_array(&mut self, field_idx: usize) {
+        if let Some(stats) = &mut self.stats {
+            for value in &self.array_buf {
+                stats[field_idx].record(*value);
+            }
+        }
+    }
+
+    /// Picks a symbol, returning its index in the pool so dependent columns can refer to it
+    fn generate_symbol(&mut self, symbol_col: usize) -> usize {
+        self.symbol_pools[symbol_col].sample(&mut self.rng)
+    }
+
+    /// True with probability `p`, used for sparse columns and null injection
+    fn chance(&mut self, p: f64) -> bool {
+        self.rng.random_bool(p)
+    }
+
+    fn symbol(&self, symbol_col: usize, idx: usize) -> &str {
+        &self.symbol_pools[symbol_col].values[idx]
+    }
+
+    /// Correlation id for the next row, starting a new event once the current one is complete
+    fn next_event_id(&mut self, sender_id: u16, size: (u32, u32)) -> &str {
+        if self.event_rows_left == 0 {
+            self.event_rows_left = self.rng.random_range(size.0..=size.1);
+            self.events_started += 1;
+            self.event_id = format!("evt-{}-{}", sender_id, self.events_started);
+        }
+        self.event_rows_left -= 1;
+        &self.event_id
+    }
+
+    /// Next value of the counter kept for the symbol at `symbol_idx` of the symbol column's
+    /// pool, in the given field column
+    fn next_sequence(&mut self, field_idx: usize, symbol_col: usize, symbol_idx: usize) -> i64 {
+        let symbol = &self.symbol_pools[symbol_col].values[symbol_idx];
+        let counters = self.sequences.entry(field_idx).or_default();
+        // Only the first row of each value allocates its key
+        if let Some(counter) = counters.get_mut(symbol.as_str()) {
+            *counter += 1;
+            return *counter;
+        }
+        counters.insert(symbol.clone(), 1);
+        1
+    }
+
+    /// Next value of a sequence column, starting at `start` and advancing by `step`
+    fn next_counter(&mut self, field_idx: usize, (start, step): (i64, i64)) -> i64 {
+        let next = self.counters.entry(field_idx).or_insert(start);
+        let value = *next;
+        *next = next.wrapping_add(step);
+        value
+    }
+
+    /// Integer within the inclusive `range`, used for all integer column types
+    fn generate_long(&mut self, range: (i64, i64), sampler: &ValueSampler) -> i64 {
+        match sampler {
+            ValueSampler::Uniform => self.rng.random_range(range.0..=range.1),
+            ValueSampler::Normal(normal) => {
+                (normal.sample(&mut self.rng).round() as i64).clamp(range.0, range.1)
+            }
+            // Zipf ranks start at 1 and are bounded by the range size
+            ValueSampler::Zipf(zipf) => range
+                .0
+                .saturating_add(zipf.sample(&mut self.rng) as i64 - 1),
+        }
+    }
+
+    /// Single-precision value widened to f64, so it round-trips exactly through a FLOAT column
+    fn generate_float(&mut self, sampler: &ValueSampler) -> f64 {
+        (self.generate_double(sampler) as f32).into()
+    }
+
+    fn generate_double(&mut self, sampler: &ValueSampler) -> f64 {
+        match sampler {
+            ValueSampler::Uniform => self.rng.random_range(DOUBLE_RANGE.0..DOUBLE_RANGE.1),
+            ValueSampler::Normal(normal) => normal.sample(&mut self.rng),
+            ValueSampler::Zipf(zipf) => DOUBLE_RANGE.0 + zipf.sample(&mut self.rng) - 1.0,
+        }
+    }
+
+    /// Array with a random element count from `len`, each element drawn like a Double
+    fn generate_double_array(&mut self, len: (usize, usize), sampler: &ValueSampler) -> &Vec<f64> {
+        let count = self.rng.random_range(len.0..=len.1);
+        let mut values = std::mem::take(&mut self.array_buf);
+        values.clear();
+        values.extend((0..count).map(|_| self.generate_double(sampler)));
+        self.array_buf = values;
+        &self.array_buf
+    }
+
+    /// Random alphanumeric string of `len` characters
+    fn generate_string(&mut self, len: usize) -> &str {
+        self.string_buf.clear();
+        self.string_buf.extend(
+            (&mut self.rng)
+                .sample_iter(Alphanumeric)
+                .take(len)
+                .map(char::from),
+        );
+        &self.string_buf
+    }
+
+    fn generate_varchar(&mut self) -> &str {
+        let len = self.rng.random_range(VARCHAR_LEN.0..=VARCHAR_LEN.1);
+        self.generate_string(len)
+    }
+
+    /// Text form of `value`, written to the reused string buffer
+    fn format(&mut self, value: impl fmt::Display) -> &str {
+        self.string_buf.clear();
+        // Writing to a String can't fail
+        let _ = write!(self.string_buf, "{}", value);
+        &self.string_buf
+    }
+
+    /// Random address, within `cidr` if set. 0.0.0.0 is QuestDB's null, so it's never picked.
+    fn generate_ipv4(&mut self, cidr: Option<Ipv4Cidr>) -> Ipv4Addr {
+        let (network, host_mask) =
+            cidr.map_or((0, u32::MAX), |cidr| (cidr.network, cidr.host_mask()));
+        loop {
+            let addr = network | (self.rng.random::<u32>() & host_mask);
+            if addr != 0 {
+                return Ipv4Addr::from(addr);
+            }
         }
     }
 
-    fn generate_symbol(&mut self) -> &str {
-        let idx = self.rng.random_range(0..self.symbols.len());
-        &self.symbols[idx]
+    /// Random 256-bit value as QuestDB's `0x` literal of 64 hex digits, most significant limb
+    /// first, returning that limb as well
+    fn generate_long256(&mut self) -> (u64, &str) {
+        let limbs: [u64; 4] = self.rng.random();
+        self.string_buf.clear();
+        self.string_buf.push_str("0x");
+        for limb in limbs {
+            // Writing to a String can't fail
+            let _ = write!(self.string_buf, "{:016x}", limb);
+        }
+        (limbs[0], &self.string_buf)
     }
 
-    fn generate_long(&mut self) -> i64 {
-        self.rng.random_range(0..1_000_000_i64)
+    /// Random geohash of `chars` base32 characters
+    fn generate_geohash(&mut self, chars: usize) -> &str {
+        self.string_buf.clear();
+        self.string_buf
+            .extend((0..chars).map(|_| char::from(GEOHASH_BASE32[self.rng.random_range(0..32)])));
+        &self.string_buf
     }
 
-    fn generate_double(&mut self) -> f64 {
-        self.rng.random_range(0.0..100.0)
+    /// Printable ASCII character, excluding space
+    fn generate_char(&mut self) -> char {
+        char::from(self.rng.random_range(b'!'..=b'~'))
     }
 
-    fn generate_timestamp(&mut self) -> i64 {
+    fn generate_timestamp(&mut self, offset: Option<(i64, i64)>, designated_ts: i64) -> i64 {
+        if let Some((min, max)) = offset {
+            return designated_ts + self.rng.random_range(min..=max);
+        }
         // Generate random timestamps for non-designated timestamp columns
         // Random timestamp within a reasonable range around the base timestamp
         let base_nanos = self.base_timestamp.timestamp_nanos_opt().unwrap_or(0);
@@ -80,19 +461,173 @@ impl DataGenerator {
             .random_range(-86_400_000_000_000..86_400_000_000_000); // ±1 day in nanoseconds
         base_nanos + random_offset
     }
+
+    /// Shift for a column drifting by `drift` per second of designated timestamp since the start
+    fn drift_offset(&self, drift: f64, timestamp: i64) -> f64 {
+        let base_nanos = self.base_timestamp.timestamp_nanos_opt().unwrap_or(0);
+        drift * (timestamp - base_nanos) as f64 / 1_000_000_000.0
+    }
+}
+
+/// Distinct values of `ALPHABET_SYMBOL_LEN` characters an alphabet of `chars` characters
+/// makes, saturating at usize::MAX
+fn alphabet_symbol_count(chars: usize) -> usize {
+    (ALPHABET_SYMBOL_LEN.0..=ALPHABET_SYMBOL_LEN.1)
+        .map(|len| chars.saturating_pow(len as u32))
+        .fold(0, usize::saturating_add)
+}
+
+/// Marks a row symbol that was not picked from a pool (null or an event id)
+const NO_POOL_SYMBOL: usize = usize::MAX;
+
+/// A symbol column along with its null rate
+struct SymbolColumn {
+    name: String,
+    null_rate: f64,
+}
+
+/// A non-symbol column along with its per-column generation settings
+struct FieldColumn {
+    name: String,
+    col_type: ColType,
+    settings: ColumnSettings,
+    /// Index into the symbol columns when this column is a per-symbol sequence
+    sequence_symbol: Option<usize>,
+    /// This sender's first value and step when this column is a sequence
+    counter: Option<(i64, i64)>,
+    /// Inclusive bounds for integer columns, either configured or the type's default
+    int_range: (i64, i64),
+    sampler: ValueSampler,
+    /// Values of a Varchar column loaded from its seed file
+    value_pool: Option<Arc<SymbolPool>>,
+    /// Index into the field columns of the source of a derived column
+    derive_source: Option<usize>,
+}
+
+/// Characters of QuestDB's geohash base32 alphabet
+const GEOHASH_BASE32: &[u8; 32] = b"0123456789bcdefghjkmnpqrstuvwxyz";
+
+/// Range of generated Float and Double values
+const DOUBLE_RANGE: (f64, f64) = (0.0, 100.0);
+
+/// Numeric value distribution, built once per column from its settings
+#[derive(Debug)]
+enum ValueSampler {
+    Uniform,
+    Normal(Normal<f64>),
+    Zipf(Zipf<f64>),
+}
+
+impl ValueSampler {
+    /// `size` is the number of values a Zipf distribution ranks over
+    fn new(distribution: &ValueDistribution, size: f64) -> Result<Self> {
+        Ok(match distribution {
+            ValueDistribution::Uniform => Self::Uniform,
+            ValueDistribution::Normal { mean, stddev } => Self::Normal(
+                Normal::new(*mean, *stddev)
+                    .map_err(|e| anyhow::anyhow!("Invalid normal distribution: {}", e))?,
+            ),
+            ValueDistribution::Zipf { exponent } => Self::Zipf(
+                Zipf::new(size, *exponent)
+                    .map_err(|e| anyhow::anyhow!("Invalid zipf distribution: {}", e))?,
+            ),
+        })
+    }
+}
+
+/// Default value range of integer columns
+const DEFAULT_LONG_RANGE: (i64, i64) = (0, 999_999);
+
+/// Pause between row counts while waiting for the server to apply the rows sent
+const VERIFY_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Values an integer column type can hold, excluding QuestDB's null sentinels
+fn integer_bounds(col_type: &ColType) -> Option<(i64, i64)> {
+    match col_type {
+        ColType::Long => Some((i64::MIN + 1, i64::MAX)),
+        ColType::Int => Some((i64::from(i32::MIN) + 1, i32::MAX.into())),
+        ColType::Short => Some((i16::MIN.into(), i16::MAX.into())),
+        ColType::Byte => Some((i8::MIN.into(), i8::MAX.into())),
+        _ => None,
+    }
+}
+
+/// Generation state of a table whose rows another table's sender interleaves with its own
+struct MixedState {
+    data_gen: DataGenerator,
+    current_timestamp: i64,
+    rows_sent: u64,
+    /// Rows written to the current batch
+    batch_rows: u32,
+    /// Rows of the current batch already flushed, counted even if the batch then fails
+    flushed_rows: u32,
+}
+
+/// What a sender's rows need, built once as it starts rather than per batch or row, since
+/// this is the hottest loop of the blaster
+struct RowContext<'a> {
+    grid_nanos: Option<i64>,
+    row_interval: Uniform<i64>,
+    table_name: TableName<'a>,
+    symbol_names: Vec<ColumnName<'a>>,
+    field_names: Vec<ColumnName<'a>>,
+    /// Symbol pool indices picked for the current row, in symbol column order
+    row_symbols: Vec<usize>,
+    /// Numeric values generated for the current row, by field column, for derived columns
+    row_values: Vec<Option<f64>>,
+    /// Contexts of the mixed tables' rows, in `mixed` order
+    mixed: Vec<RowContext<'a>>,
 }
 
 /// Individual sender thread that blasts data to QuestDB
 struct TableSender {
     sender_id: u16,
     table_name: String,
+    /// Name in the ILP rows, the table name unless `ilp_table_name` is set
+    ilp_table_name: String,
     send_settings: SendSettings,
     ilp_connection: String,
-    rows_to_send: u64,
+    /// `None` when the sender runs for the table's `duration`, or until interrupted
+    rows_to_send: Option<u64>,
     global_sent_counter: Arc<AtomicU64>,
+    warmup: Arc<Warmup>,
+    /// Rows sent before `rows_to_send`, neither counted nor traced
+    warmup_rows: u64,
+    options: BlastOptions,
+    /// Shared by all of the table's senders when `max_rows_per_sec` is set
+    rate_limiter: Option<Arc<RateLimiter>>,
+    symbol_pools: Vec<Arc<SymbolPool>>,
+    /// Index into the symbol columns of the event id column, with the event size range
+    event_symbol: Option<(usize, (u32, u32))>,
+    columns_present_ratio: Option<f64>,
+    seed: Option<u64>,
+    partition_by: PartitionBy,
+    has_designated_ts: bool,
+    ts_precision: TsPrecision,
+    /// Start of this sender's own timestamp window when `ordered_timestamps` or a
+    /// historical window is set
+    start_timestamp: Option<i64>,
+    /// Exact per-row timestamp increment spreading rows over a historical window
+    fixed_increment: Option<i64>,
+    /// Collects the values this sender generated when `column_stats` is enabled
+    stats: Option<Arc<StatsCollector>>,
+    /// Where this sender stopped in the run being resumed
+    resumed: Option<SenderProgress>,
+    /// This sender's flush latencies after warmup, merged into `latency` as it finishes
+    flush_latencies: RefCell<Histogram<u64>>,
+    latency: Arc<LatencyCollector>,
+    /// The table's live metrics when `metrics_port` is set
+    metrics: Option<Arc<TableMetrics>>,
+    /// Senders of the `mixed_with` tables, whose rows this sender interleaves with its own
+    mixed: Vec<TableSender>,
+    /// Where each mixed table's rows are, set up once the sender starts
+    mixed_state: RefCell<Vec<MixedState>>,
     // Pre-sorted and pre-validated columns for efficient ILP serialization
-    symbol_columns: Vec<String>,
-    field_columns: Vec<(String, ColType)>,
+    symbol_columns: Vec<SymbolColumn>,
+    field_columns: Vec<FieldColumn>,
+    /// HTTP auto-flush, flushing mid-batch by row count or time since the last flush
+    auto_flush_rows: Option<usize>,
+    auto_flush_interval: Option<Duration>,
 }
 
 impl TableSender {
@@ -104,52 +639,137 @@ impl TableSender {
 
         let mut rows_sent = 0u64;
         let mut batches_sent = 0u16;
-        let mut current_timestamp = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_nanos() as i64;
-
-        // Add small random offset to avoid all senders starting at exact same timestamp
-        let mut rng = rand::rng();
-        current_timestamp += rng.random_range(0..1_000_000_000); // 0-1 second offset
-
-        let mut data_gen = DataGenerator::new(DateTime::from_timestamp_nanos(current_timestamp));
+        let mut rng = seeded_rng(self.seed, self.sender_id.into());
+        let (mut current_timestamp, mut data_gen) = self.start_generator(&mut rng);
+        let mut ctx = self.row_context()?;
+        *self.mixed_state.borrow_mut() = self
+            .mixed
+            .iter()
+            .map(|member| {
+                let mut rng = seeded_rng(member.seed, member.sender_id.into());
+                let (current_timestamp, data_gen) = member.start_generator(&mut rng);
+                MixedState {
+                    data_gen,
+                    current_timestamp,
+                    rows_sent: 0,
+                    batch_rows: 0,
+                    flushed_rows: 0,
+                }
+            })
+            .collect();
         let mut client: Option<(QuestDbSender, Buffer)> = None;
+        let mut reconnects = 0u32;
+        let mut warmup_left = self.warmup_rows;
+        let mut last_logged = (Instant::now(), 0u64);
+        // When the next batch is due at `target_rate`
+        let mut next_due: Option<Instant> = None;
+        // A `duration` is measured from the end of the sender's warmup
+        let mut deadline = None;
+        if warmup_left == 0 {
+            self.warmup.finish_sender();
+        }
+
+        while warmup_left > 0 || self.group_rows_left(rows_sent, deadline) > 0 {
+            // Batches are flushed as a whole, so stopping here leaves nothing unsent
+            if self.options.shutdown.load(Ordering::Relaxed) {
+                info!(
+                    "Sender {} stopping early after {}",
+                    self.sender_id,
+                    self.describe_sent(rows_sent)
+                );
+                self.merge_stats(&data_gen);
+                return Ok(());
+            }
+            if warmup_left == 0 && deadline.is_none() {
+                deadline = self
+                    .send_settings
+                    .duration
+                    .map(|duration| Instant::now() + duration);
+            }
 
-        while rows_sent < self.rows_to_send {
             // Connect if needed
             if client.is_none() {
-                client = Some(self.connect_ilp()?);
+                client = Some(match self.connect_ilp() {
+                    Ok(client) => client,
+                    Err(e) if reconnects > 0 && reconnects < self.send_settings.max_reconnects => {
+                        reconnects += 1;
+                        warn!(
+                            "Sender {} failed to reconnect (attempt {}/{}): {:#}",
+                            self.sender_id, reconnects, self.send_settings.max_reconnects, e
+                        );
+                        thread::sleep(self.send_settings.retry.initial_backoff);
+                        continue;
+                    }
+                    Err(e) => return Err(e),
+                });
                 debug!("Sender {} connected to ILP", self.sender_id);
             }
 
-            // Generate random batch size
-            let batch_size =
-                rng.random_range(self.send_settings.batch_size.0..=self.send_settings.batch_size.1);
-            let rows_remaining = self.rows_to_send - rows_sent;
-            let actual_batch_size = std::cmp::min(batch_size as u64, rows_remaining) as u32;
+            let batch_started = Instant::now();
+            // Warmup batches stop at the end of the warmup, so every measured batch is whole
+            let rows_remaining = if warmup_left > 0 {
+                warmup_left
+            } else {
+                self.group_rows_left(rows_sent, deadline)
+            };
+            let actual_batch_size =
+                random_batch_size(self.send_settings.batch_size, rows_remaining, &mut rng);
 
             // Send batch
-            let (sender, buffer) = client.as_mut().unwrap();
-            self.send_batch(
-                sender,
-                buffer,
-                &mut data_gen,
+            let mut flushed = 0;
+            if let Err(e) = self.send_batch(
+                client.as_mut().unwrap(),
+                (&mut data_gen, &mut ctx),
                 &mut current_timestamp,
                 actual_batch_size,
-            )?;
+                rows_sent,
+                &mut flushed,
+            ) {
+                if !is_connection_error(&e) || reconnects >= self.send_settings.max_reconnects {
+                    return Err(e);
+                }
+                // Rows flushed by `flush_bytes_threshold` before the failure are counted, each
+                // toward its own table, the unacknowledged rest is discarded with the old
+                // buffer and generated afresh
+                self.count_sent(flushed, &mut warmup_left, &mut rows_sent);
+                self.count_mixed_sent();
+                reconnects += 1;
+                warn!(
+                    "Sender {} lost its connection, reconnecting (attempt {}/{}): {:#}",
+                    self.sender_id, reconnects, self.send_settings.max_reconnects, e
+                );
+                client = None;
+                batches_sent = 0;
+                continue;
+            }
 
-            rows_sent += actual_batch_size as u64;
             batches_sent += 1;
-
-            // Update global counter
-            self.global_sent_counter
-                .fetch_add(actual_batch_size as u64, Ordering::Relaxed);
+            let mixed_rows = self.count_mixed_sent();
+            self.count_sent(
+                actual_batch_size - mixed_rows,
+                &mut warmup_left,
+                &mut rows_sent,
+            );
+            if warmup_left == 0 {
+                self.save_progress(rows_sent, current_timestamp, &data_gen);
+            }
 
             debug!(
                 "Sender {} sent batch {}, {} rows total",
                 self.sender_id, batches_sent, rows_sent
             );
+            if let Some(interval) = self.send_settings.log_every
+                && last_logged.0.elapsed() >= interval
+            {
+                let (logged_at, logged_rows) = last_logged;
+                info!(
+                    "Sender {} sent {}, {:.0} rows/sec since the last update",
+                    self.sender_id,
+                    self.describe_sent(rows_sent),
+                    (rows_sent - logged_rows) as f64 / logged_at.elapsed().as_secs_f64()
+                );
+                last_logged = (Instant::now(), rows_sent);
+            }
 
             // Check if we need to disconnect
             if batches_sent >= self.send_settings.batches_connection_keepalive {
@@ -171,11 +791,21 @@ impl TableSender {
             }
 
             // Pause if not done
-            if rows_sent < self.rows_to_send {
-                let pause_duration = Duration::from_nanos(rng.random_range(
-                    self.send_settings.batch_pause.0.as_nanos()
-                        ..=self.send_settings.batch_pause.1.as_nanos(),
-                ) as u64);
+            if self.group_rows_left(rows_sent, deadline) > 0 {
+                let pause_duration = match self.send_settings.target_rate {
+                    // Closed loop: each batch is due once the previous one's rows took their
+                    // share of the rate, however long sending them took, so a slow batch is
+                    // made up for by a shorter pause rather than drifting the cadence
+                    Some(target_rate) => {
+                        let sender_rate =
+                            target_rate as f64 / f64::from(self.send_settings.parallel_senders);
+                        let due = next_due.unwrap_or(batch_started)
+                            + Duration::from_secs_f64(f64::from(actual_batch_size) / sender_rate);
+                        next_due = Some(due);
+                        due.saturating_duration_since(Instant::now())
+                    }
+                    None => random_pause(&self.send_settings, &mut rng),
+                };
                 debug!("Sender {} pausing for {:?}", self.sender_id, pause_duration);
                 thread::sleep(pause_duration);
             }
@@ -185,9 +815,139 @@ impl TableSender {
             "Sender {} completed, sent {} rows",
             self.sender_id, rows_sent
         );
+        self.merge_stats(&data_gen);
         Ok(())
     }
 
+    /// First timestamp and value generator of the sender, continuing where a resumed run
+    /// stopped
+    fn start_generator(&self, rng: &mut StdRng) -> (i64, DataGenerator) {
+        let current_timestamp = match self.start_timestamp {
+            Some(start) => start,
+            None => {
+                // Add small random offset to avoid all senders starting at exact same timestamp
+                now_nanos() + rng.random_range(0..1_000_000_000) // 0-1 second offset
+            }
+        };
+
+        let mut data_gen = DataGenerator::new(
+            DateTime::from_timestamp_nanos(current_timestamp),
+            self.symbol_pools.clone(),
+            StdRng::from_rng(rng),
+        );
+        if self.stats.is_some() {
+            data_gen.stats = Some(vec![ValueStats::default(); self.field_columns.len()]);
+        }
+        if let Some(resumed) = &self.resumed {
+            data_gen.counters.extend(resumed.counters.iter().copied());
+            for (field_idx, symbol, value) in &resumed.sequences {
+                data_gen
+                    .sequences
+                    .entry(*field_idx)
+                    .or_default()
+                    .insert(symbol.clone(), *value);
+            }
+            data_gen.events_started = resumed.events_started;
+        }
+        (current_timestamp, data_gen)
+    }
+
+    /// Rows still to send, of the mixed tables too
+    fn group_rows_left(&self, rows_sent: u64, deadline: Option<Instant>) -> u64 {
+        let mixed_state = self.mixed_state.borrow();
+        self.mixed.iter().zip(mixed_state.iter()).fold(
+            self.rows_left(rows_sent, deadline),
+            |rows, (member, state)| rows.saturating_add(member.rows_left(state.rows_sent, None)),
+        )
+    }
+
+    /// Counts the mixed tables' flushed rows of a batch, whether it was sent whole or failed
+    /// part way, and starts their next batch. Returns how many rows were counted.
+    fn count_mixed_sent(&self) -> u32 {
+        let mut mixed_state = self.mixed_state.borrow_mut();
+        let mut rows = 0;
+        for (member, state) in self.mixed.iter().zip(mixed_state.iter_mut()) {
+            // Mixed tables have no warmup
+            member.count_sent(state.flushed_rows, &mut 0, &mut state.rows_sent);
+            rows += state.flushed_rows;
+            state.batch_rows = 0;
+            state.flushed_rows = 0;
+        }
+        rows
+    }
+
+    /// Marks every row written to the batch so far as flushed, the table's own in `flushed`
+    /// and each mixed table's in its state
+    fn mark_flushed(own_rows: u32, flushed: &mut u32, mixed_state: &mut [MixedState]) {
+        *flushed = own_rows;
+        for state in mixed_state {
+            state.flushed_rows = state.batch_rows;
+        }
+    }
+
+    /// Rows still to send, unbounded until the deadline when running for a `duration`, or
+    /// until shut down when `tot_rows` is 0
+    fn rows_left(&self, rows_sent: u64, deadline: Option<Instant>) -> u64 {
+        match self.rows_to_send {
+            Some(rows) => rows - rows_sent,
+            None if deadline.is_some_and(|deadline| Instant::now() >= deadline) => 0,
+            None => u64::MAX,
+        }
+    }
+
+    fn describe_sent(&self, rows_sent: u64) -> String {
+        match self.rows_to_send {
+            Some(rows) => format!("{} of {} rows", rows_sent, rows),
+            None => format!("{} rows", rows_sent),
+        }
+    }
+
+    /// Records where the sender is for `--resume`, once its rows are flushed
+    fn save_progress(&self, rows_sent: u64, current_timestamp: i64, data_gen: &DataGenerator) {
+        // Only the table's own rows would be recorded, so mixed senders aren't resumable
+        let Some(checkpoint) = &self.options.checkpoint else {
+            return;
+        };
+        if !self.mixed.is_empty() {
+            return;
+        }
+        let resumed_rows = self.resumed.as_ref().map_or(0, |resumed| resumed.rows_sent);
+        checkpoint.update(
+            &self.table_name,
+            self.send_settings.parallel_senders,
+            self.sender_id,
+            SenderProgress {
+                rows_sent: resumed_rows + rows_sent,
+                timestamp: current_timestamp,
+                counters: data_gen
+                    .counters
+                    .iter()
+                    .map(|(&field_idx, &value)| (field_idx, value))
+                    .collect(),
+                sequences: data_gen
+                    .sequences
+                    .iter()
+                    .flat_map(|(&field_idx, counters)| {
+                        counters
+                            .iter()
+                            .map(move |(symbol, &value)| (field_idx, symbol.clone(), value))
+                    })
+                    .collect(),
+                events_started: data_gen.events_started,
+            },
+        );
+    }
+
+    fn merge_stats(&self, data_gen: &DataGenerator) {
+        if let (Some(collector), Some(stats)) = (&self.stats, &data_gen.stats) {
+            collector.merge(stats);
+        }
+        self.latency.merge(&self.flush_latencies.borrow());
+        for (member, state) in self.mixed.iter().zip(self.mixed_state.borrow().iter()) {
+            member.merge_stats(&state.data_gen);
+        }
+    }
+
     fn connect_ilp(&self) -> Result<(QuestDbSender, Buffer)> {
         let sender = QuestDbSender::from_conf(&self.ilp_connection)
             .context("Failed to create QuestDB ILP sender")?;
@@ -195,228 +955,2690 @@ impl TableSender {
         Ok((sender, buffer))
     }
 
+    /// Counts flushed rows toward the warmup while it lasts, and toward `rows_to_send` after
+    fn count_sent(&self, rows: u32, warmup_left: &mut u64, rows_sent: &mut u64) {
+        if *warmup_left > 0 {
+            // Warmup batches never extend past the warmup
+            *warmup_left -= u64::from(rows);
+            if *warmup_left == 0 {
+                info!("Sender {} finished its warmup", self.sender_id);
+                self.warmup.finish_sender();
+            }
+        } else {
+            *rows_sent += u64::from(rows);
+
+            // Update global counter
+            self.global_sent_counter
+                .fetch_add(u64::from(rows), Ordering::Relaxed);
+        }
+    }
+
+    /// Generates and flushes a batch, flushing early whenever the buffer reaches
+    /// `flush_bytes_threshold` or the HTTP auto-flush is due. `flushed` tracks the table's
+    /// own rows already flushed, and the mixed state those of the mixed tables, should it fail.
+    /// Rows of any mixed tables are interleaved round-robin with the table's own.
     fn send_batch(
         &self,
-        sender: &mut QuestDbSender,
-        buffer: &mut Buffer,
-        data_gen: &mut DataGenerator,
+        (sender, buffer): &mut (QuestDbSender, Buffer),
+        (data_gen, ctx): (&mut DataGenerator, &mut RowContext),
         current_timestamp: &mut i64,
         batch_size: u32,
+        rows_sent: u64,
+        flushed: &mut u32,
     ) -> Result<()> {
+        if let Some(limiter) = &self.rate_limiter {
+            limiter.acquire(batch_size);
+        }
+
+        let mut mixed_state = self.mixed_state.borrow_mut();
+        let mut own_rows = 0u32;
+        let mut turn = 0;
+        let mut last_flush = Instant::now();
+
         for _ in 0..batch_size {
-            // Increment timestamp for each row
-            *current_timestamp += rand::rng().random_range(1_000_000..10_000_000); // 1-10ms increment
-
-            // Start building a row for the table (unchecked - validated at startup)
-            let table_name = TableName::new_unchecked(self.table_name.as_str());
-            buffer.table(table_name)?;
-
-            // 1. First, serialize all symbols
-            for col_name_str in &self.symbol_columns {
-                let col_name = ColumnName::new_unchecked(col_name_str.as_str());
-                let value = data_gen.generate_symbol();
-                buffer.symbol(col_name, value)?;
-            }
-
-            // 2. Then, all remaining non-symbol columns (except designated timestamp)
-            for (col_name_str, col_type) in &self.field_columns {
-                let col_name = ColumnName::new_unchecked(col_name_str.as_str());
-                match col_type {
-                    ColType::Long => {
-                        let value = data_gen.generate_long();
-                        buffer.column_i64(col_name, value)?;
-                    }
-                    ColType::Double => {
-                        let value = data_gen.generate_double();
-                        buffer.column_f64(col_name, value)?;
+            if self.mixed.is_empty() {
+                self.write_row(buffer, data_gen, current_timestamp, ctx)?;
+                own_rows += 1;
+            } else {
+                // The batch takes turns between the table and each mixed table with rows
+                // left, and never has more rows than they have left together
+                let table = loop {
+                    let table = turn % (self.mixed.len() + 1);
+                    turn += 1;
+                    let has_rows = match table.checked_sub(1) {
+                        None => u64::from(own_rows) < self.rows_left(rows_sent, None),
+                        Some(idx) => {
+                            let state = &mixed_state[idx];
+                            u64::from(state.batch_rows)
+                                < self.mixed[idx].rows_left(state.rows_sent, None)
+                        }
+                    };
+                    if has_rows {
+                        break table;
                     }
-                    ColType::Timestamp => {
-                        // Non-designated timestamp fields
-                        let value = data_gen.generate_timestamp();
-                        buffer.column_ts(col_name, TimestampNanos::new(value))?;
+                };
+                match table.checked_sub(1) {
+                    None => {
+                        self.write_row(buffer, data_gen, current_timestamp, ctx)?;
+                        own_rows += 1;
                     }
-                    ColType::Symbol => {
-                        // Symbols should not be in field_columns
-                        unreachable!("Symbols should be in symbol_columns, not field_columns");
+                    Some(idx) => {
+                        let state = &mut mixed_state[idx];
+                        self.mixed[idx].write_row(
+                            buffer,
+                            &mut state.data_gen,
+                            &mut state.current_timestamp,
+                            &mut ctx.mixed[idx],
+                        )?;
+                        state.batch_rows += 1;
                     }
                 }
             }
 
-            // 3. Lastly, set the designated timestamp
-            buffer.at(TimestampNanos::new(*current_timestamp))?;
+            let flush_due = self
+                .send_settings
+                .flush_bytes_threshold
+                .is_some_and(|threshold| buffer.len() >= threshold)
+                || self
+                    .auto_flush_rows
+                    .is_some_and(|rows| buffer.row_count() >= rows)
+                || self
+                    .auto_flush_interval
+                    .is_some_and(|interval| last_flush.elapsed() >= interval);
+            if flush_due {
+                let rows = buffer.row_count() as u32;
+                self.flush(sender, buffer, rows, rows_sent + u64::from(*flushed))?;
+                Self::mark_flushed(own_rows, flushed, &mut mixed_state);
+                last_flush = Instant::now();
+            }
         }
 
-        sender
-            .flush(buffer)
-            .context("Failed to flush batch to QuestDB")?;
-
+        if buffer.row_count() > 0 {
+            let rows = buffer.row_count() as u32;
+            self.flush(sender, buffer, rows, rows_sent + u64::from(*flushed))?;
+            Self::mark_flushed(own_rows, flushed, &mut mixed_state);
+        }
         Ok(())
     }
-}
-
-/// Orchestrates the blasting process for a single table
-pub fn blast_table(table_name: &str, table_config: &Table, connection: &Connection) -> Result<()> {
-    info!("Blasting table '{}'", table_name);
-
-    // Validate table and column names at startup
-    validate_names(table_name, table_config)?;
-
-    // Drop and recreate table
-    drop_and_create_table(table_name, table_config, &connection.pgsql)?;
-
-    // Calculate rows per sender
-    let total_rows = table_config.send.tot_rows;
-    let parallel_senders = table_config.send.parallel_senders;
-    let base_rows_per_sender = total_rows / parallel_senders as u64;
-    let extra_rows = total_rows % parallel_senders as u64;
 
-    info!(
-        "Distributing {} total rows across {} senders ({} base + {} extra)",
-        total_rows, parallel_senders, base_rows_per_sender, extra_rows
-    );
-
-    // Global counter for progress tracking
-    let global_sent_counter = Arc::new(AtomicU64::new(0));
+    /// What the table's rows and those of its mixed tables need, for the whole run
+    fn row_context(&self) -> Result<RowContext<'_>> {
+        Ok(RowContext {
+            grid_nanos: self
+                .send_settings
+                .ts_grid
+                .map(|grid| grid.as_nanos() as i64),
+            row_interval: Uniform::new_inclusive(
+                self.send_settings.row_interval.0.as_nanos() as i64,
+                self.send_settings.row_interval.1.as_nanos() as i64,
+            )
+            .map_err(|e| anyhow::anyhow!("Invalid row_interval: {}", e))?,
+            // Names are wrapped once per sender instead of per batch, row and cell (unchecked -
+            // validated at startup)
+            table_name: TableName::new_unchecked(self.ilp_table_name.as_str()),
+            symbol_names: self
+                .symbol_columns
+                .iter()
+                .map(|column| ColumnName::new_unchecked(column.name.as_str()))
+                .collect(),
+            field_names: self
+                .field_columns
+                .iter()
+                .map(|column| ColumnName::new_unchecked(column.name.as_str()))
+                .collect(),
+            row_symbols: Vec::with_capacity(self.symbol_columns.len()),
+            row_values: vec![None; self.field_columns.len()],
+            mixed: self
+                .mixed
+                .iter()
+                .map(TableSender::row_context)
+                .collect::<Result<_>>()?,
+        })
+    }
 
-    // Spawn sender threads
-    let mut handles = Vec::new();
-    for sender_id in 0..parallel_senders {
-        let rows_for_this_sender =
-            base_rows_per_sender + if sender_id < extra_rows as u16 { 1 } else { 0 };
+    /// Generates one row of the table into the buffer
+    fn write_row(
+        &self,
+        buffer: &mut Buffer,
+        data_gen: &mut DataGenerator,
+        current_timestamp: &mut i64,
+        ctx: &mut RowContext,
+    ) -> Result<()> {
+        if self.send_settings.ts_mode == TsMode::Wallclock {
+            // A live feed stamps rows with the time they're generated at, never going back
+            // should the clock be adjusted
+            *current_timestamp = now_nanos().max(*current_timestamp);
+        } else {
+            // Increment timestamp for each row
+            let mut increment = ctx.row_interval.sample(&mut data_gen.rng);
+            if let Some(step) = self.fixed_increment {
+                increment = step;
+            } else if let Some(grid) = ctx.grid_nanos {
+                // Advance at least one grid step so rounding never collapses rows onto one point
+                increment = increment.max(grid);
+            }
+            *current_timestamp = match &self.send_settings.activity_profile {
+                Some(profile) => profile.advance(*current_timestamp, increment),
+                None => *current_timestamp + increment,
+            };
+        }
 
-        // Pre-sort columns for efficient ILP serialization
-        let mut symbol_columns = Vec::new();
-        let mut field_columns = Vec::new();
+        // Start building a row for the table
+        buffer.table(ctx.table_name)?;
 
-        for (col_name, col_type) in &table_config.schema {
-            if col_name == &table_config.designated_ts {
-                // Designated timestamp is handled separately
+        // 1. First, serialize all symbols
+        ctx.row_symbols.clear();
+        let mut row_empty = true;
+        for (symbol_col, column) in self.symbol_columns.iter().enumerate() {
+            let col_name = ctx.symbol_names[symbol_col];
+            if let Some((event_col, event_size)) = self.event_symbol
+                && event_col == symbol_col
+            {
+                let event_id = data_gen.next_event_id(self.sender_id, event_size);
+                buffer.symbol(col_name, event_id)?;
+                // Validation keeps sequences from depending on the event id
+                ctx.row_symbols.push(NO_POOL_SYMBOL);
+                row_empty = false;
                 continue;
             }
-
-            match col_type {
-                ColType::Symbol => symbol_columns.push(col_name.clone()),
-                ColType::Long | ColType::Double | ColType::Timestamp => {
-                    field_columns.push((col_name.clone(), col_type.clone()));
-                }
+            // Skipping the column makes QuestDB store a null, unless the row would be empty
+            let last_column =
+                self.field_columns.is_empty() && symbol_col == self.symbol_columns.len() - 1;
+            if column.null_rate > 0.0
+                && data_gen.chance(column.null_rate)
+                && !(row_empty && last_column)
+            {
+                ctx.row_symbols.push(NO_POOL_SYMBOL);
+                continue;
             }
+            let symbol_idx = data_gen.generate_symbol(symbol_col);
+            buffer.symbol(col_name, data_gen.symbol(symbol_col, symbol_idx))?;
+            ctx.row_symbols.push(symbol_idx);
+            row_empty = false;
         }
 
-        let sender = TableSender {
-            sender_id,
-            table_name: table_name.to_string(),
-            send_settings: table_config.send.clone(),
-            ilp_connection: connection.ilp.clone(),
-            rows_to_send: rows_for_this_sender,
-            global_sent_counter: Arc::clone(&global_sent_counter),
-            symbol_columns,
-            field_columns,
-        };
-
-        info!(
-            "Starting sender {} with {} rows to send",
-            sender_id, rows_for_this_sender
-        );
-
-        let handle = thread::spawn(move || {
-            if let Err(e) = sender.run() {
-                error!("Sender {} failed: {}", sender_id, e);
-                return Err(e);
+        // 2. Then, all remaining non-symbol columns (except designated timestamp)
+        ctx.row_values.fill(None);
+        for (field_idx, column) in self.field_columns.iter().enumerate() {
+            // Sparse rows drop columns at random and nulls skip them, a sequence or derived
+            // column is null whenever its source is, but a row needs at least one column
+            let sequence_null = column
+                .sequence_symbol
+                .is_some_and(|symbol_col| ctx.row_symbols[symbol_col] == NO_POOL_SYMBOL);
+            let source_value = column.derive_source.map(|source| ctx.row_values[source]);
+            let dropped = self
+                .columns_present_ratio
+                .is_some_and(|ratio| !data_gen.chance(ratio));
+            let null_rate = column.settings.null_rate;
+            let null = sequence_null
+                || source_value == Some(None)
+                || (null_rate > 0.0 && data_gen.chance(null_rate));
+            if (dropped || null) && !(row_empty && field_idx == self.field_columns.len() - 1) {
+                data_gen.record_null(field_idx);
+                continue;
             }
-            Ok(())
-        });
-
-        handles.push(handle);
-    }
+            row_empty = false;
+
+            let col_name = ctx.field_names[field_idx];
+            if let Some(source_value) = source_value {
+                // Only a row's last column can be forced out of a null source
+                ctx.row_values[field_idx] = self.write_derived(
+                    buffer,
+                    data_gen,
+                    col_name,
+                    column,
+                    field_idx,
+                    source_value.unwrap_or_default(),
+                )?;
+                continue;
+            }
+            let drift = data_gen.drift_offset(column.settings.drift, *current_timestamp);
+            match column.col_type {
+                ColType::Long => {
+                    let value = match (column.counter, column.sequence_symbol) {
+                        (Some(counter), _) => data_gen.next_counter(field_idx, counter),
+                        (None, Some(symbol_col))
+                            if ctx.row_symbols[symbol_col] != NO_POOL_SYMBOL =>
+                        {
+                            data_gen.next_sequence(
+                                field_idx,
+                                symbol_col,
+                                ctx.row_symbols[symbol_col],
+                            )
+                        }
+                        // Drifted values saturate at the column's range rather than overflow
+                        _ => data_gen
+                            .generate_long(column.int_range, &column.sampler)
+                            .saturating_add(drift as i64)
+                            .clamp(column.int_range.0, column.int_range.1),
+                    };
+                    buffer.column_i64(col_name, value)?;
+                    data_gen.record(field_idx, value as f64);
+                    ctx.row_values[field_idx] = Some(value as f64);
+                }
+                // ILP only carries i64 integers, the range keeps them within the narrower type,
+                // drifted or not
+                ColType::Int | ColType::Short | ColType::Byte => {
+                    let value = data_gen
+                        .generate_long(column.int_range, &column.sampler)
+                        .saturating_add(drift as i64)
+                        .clamp(column.int_range.0, column.int_range.1);
+                    buffer.column_i64(col_name, value)?;
+                    data_gen.record(field_idx, value as f64);
+                    ctx.row_values[field_idx] = Some(value as f64);
+                }
+                // ILP only carries f64, the server narrows it on ingestion
+                ColType::Float => {
+                    let value = data_gen.generate_float(&column.sampler) + drift;
+                    buffer.column_f64(col_name, value)?;
+                    data_gen.record(field_idx, value);
+                    ctx.row_values[field_idx] = Some(value);
+                }
+                ColType::Double => {
+                    let value = data_gen.generate_double(&column.sampler) + drift;
+                    buffer.column_f64(col_name, value)?;
+                    data_gen.record(field_idx, value);
+                    ctx.row_values[field_idx] = Some(value);
+                }
+                ColType::Varchar => {
+                    let len = match &column.value_pool {
+                        Some(pool) => {
+                            let idx = pool.sample(&mut data_gen.rng);
+                            buffer.column_str(col_name, &pool.values[idx])?;
+                            pool.values[idx].len()
+                        }
+                        None => {
+                            let value = data_gen.generate_varchar();
+                            buffer.column_str(col_name, value)?;
+                            value.len()
+                        }
+                    };
+                    data_gen.record(field_idx, len as f64);
+                }
+                // ILP has no char type, the server converts the one-character string
+                ColType::Char => {
+                    let value = data_gen.generate_char();
+                    buffer.column_str(col_name, value.encode_utf8(&mut [0; 4]))?;
+                    data_gen.record(field_idx, u32::from(value) as f64);
+                }
+                // Sent as text, the server parses it into the IPV4 column
+                ColType::Ipv4 => {
+                    let value = data_gen.generate_ipv4(column.settings.cidr);
+                    buffer.column_str(col_name, data_gen.format(value))?;
+                    data_gen.record(field_idx, u32::from(value) as f64);
+                }
+                // The client has no long256 API, the server parses the literal into the column
+                ColType::Long256 => {
+                    let (high_limb, value) = data_gen.generate_long256();
+                    buffer.column_str(col_name, value)?;
+                    data_gen.record(field_idx, high_limb as f64);
+                }
+                // Sent as text, the server truncates the last character to the column's bits
+                ColType::GeoHash { precision } => {
+                    let value = data_gen.generate_geohash(precision.chars());
+                    buffer.column_str(col_name, value)?;
+                }
+                ColType::DoubleArray => {
+                    let array_len = column.settings.array_len.unwrap_or(DEFAULT_ARRAY_LEN);
+                    let value = data_gen.generate_double_array(array_len, &column.sampler);
+                    buffer.column_arr(col_name, value)?;
+                    data_gen.record_array(field_idx);
+                }
+                ColType::Timestamp => {
+                    // Non-designated timestamp fields
+                    let value =
+                        data_gen.generate_timestamp(column.settings.offset, *current_timestamp);
+                    buffer.column_ts(col_name, ilp_timestamp(self.ts_precision, value))?;
+                    data_gen.record(field_idx, value as f64);
+                }
+                // ILP has no date type, the server casts the integer millis into the column
+                ColType::Date => {
+                    let value = data_gen
+                        .generate_timestamp(column.settings.offset, *current_timestamp)
+                        .div_euclid(1_000_000);
+                    buffer.column_i64(col_name, value)?;
+                    data_gen.record(field_idx, value as f64);
+                }
+                ColType::Symbol => {
+                    // Symbols should not be in field_columns
+                    unreachable!("Symbols should be in symbol_columns, not field_columns");
+                }
+            }
+        }
+
+        // 3. Lastly, set the designated timestamp, or let the server assign one
+        if self.has_designated_ts {
+            let mut designated_ts = *current_timestamp;
+            if data_gen.chance(self.send_settings.out_of_order_rate) {
+                let lag = self.send_settings.out_of_order_lag.as_nanos() as i64;
+                let floor = partition_start(self.partition_by, designated_ts);
+                designated_ts = (designated_ts - data_gen.rng.random_range(1..=lag)).max(floor);
+            }
+            if let Some(grid) = ctx.grid_nanos {
+                designated_ts -= designated_ts.rem_euclid(grid);
+            }
+            buffer.at(ilp_timestamp(self.ts_precision, designated_ts))?;
+        } else {
+            buffer.at_now()?;
+        }
+        Ok(())
+    }
+
+    /// Writes a derived column's value computed from its source's, returning the numeric
+    /// value for columns derived from this one in turn
+    fn write_derived(
+        &self,
+        buffer: &mut Buffer,
+        data_gen: &mut DataGenerator,
+        col_name: ColumnName,
+        column: &FieldColumn,
+        field_idx: usize,
+        source_value: f64,
+    ) -> Result<Option<f64>> {
+        match column.settings.derive.as_ref() {
+            Some(Derivation::Linear {
+                factor,
+                offset,
+                jitter,
+                ..
+            }) => {
+                let noise = if *jitter > 0.0 {
+                    data_gen.rng.random_range(-jitter..=*jitter)
+                } else {
+                    0.0
+                };
+                let value = source_value * factor + offset + noise;
+                if matches!(column.col_type, ColType::Float | ColType::Double) {
+                    buffer.column_f64(col_name, value)?;
+                    data_gen.record(field_idx, value);
+                    return Ok(Some(value));
+                }
+                // Integer columns round and stay within their range
+                let value = (value.round() as i64).clamp(column.int_range.0, column.int_range.1);
+                buffer.column_i64(col_name, value)?;
+                data_gen.record(field_idx, value as f64);
+                Ok(Some(value as f64))
+            }
+            Some(Derivation::Buckets {
+                thresholds, values, ..
+            }) => {
+                let value =
+                    &values[thresholds.partition_point(|threshold| *threshold <= source_value)];
+                buffer.column_str(col_name, value)?;
+                data_gen.record(field_idx, value.len() as f64);
+                Ok(None)
+            }
+            None => unreachable!("Only derived columns have a derive source"),
+        }
+    }
+
+    /// Flushes the buffered `batch_size` rows, running the pre-flush hook and tracing the
+    /// flush latency
+    fn flush(
+        &self,
+        sender: &mut QuestDbSender,
+        buffer: &mut Buffer,
+        batch_size: u32,
+        rows_sent: u64,
+    ) -> Result<()> {
+        let ctx = FlushContext {
+            table_name: &self.table_name,
+            sender_id: self.sender_id,
+            batch_size,
+            rows_sent,
+        };
+        self.options
+            .pre_flush_hook
+            .before_flush(&ctx, buffer)
+            .context("Pre-flush hook failed")?;
+
+        // The client does not expose on-wire (post-compression) sizes, only the buffered bytes
+        debug!(
+            "Sender {} flushing {} uncompressed bytes",
+            self.sender_id,
+            buffer.len()
+        );
+
+        let flush_timestamp = now_nanos();
+        let flush_start = Instant::now();
+        self.flush_with_retry(sender, buffer)?;
+        let flush_latency = flush_start.elapsed();
+
+        if self.warmup.is_done() {
+            latency::record(&mut self.flush_latencies.borrow_mut(), flush_latency);
+            if let Some(metrics) = &self.metrics {
+                metrics.observe_flush(flush_latency);
+            }
+        }
+        if let Some(trace) = &self.options.latency_trace
+            && self.warmup.is_done()
+        {
+            trace.record(
+                flush_timestamp,
+                &self.table_name,
+                self.sender_id,
+                batch_size,
+                flush_latency,
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Flushes the buffer, retrying with exponential backoff. A failed flush leaves the
+    /// buffer intact, so each retry resends the same rows and nothing is counted twice.
+    fn flush_with_retry(&self, sender: &mut QuestDbSender, buffer: &mut Buffer) -> Result<()> {
+        let retry = &self.send_settings.retry;
+        let mut backoff = retry.initial_backoff;
+        let mut attempt = 1;
+        loop {
+            match sender.flush(buffer) {
+                Ok(()) => return Ok(()),
+                Err(e) if attempt < retry.max_attempts => {
+                    self.note_flush_failure();
+                    warn!(
+                        "Sender {} flush attempt {}/{} failed, retrying in {:?}: {}",
+                        self.sender_id, attempt, retry.max_attempts, backoff, e
+                    );
+                    thread::sleep(backoff);
+                    backoff = (backoff * 2).min(retry.max_backoff);
+                    attempt += 1;
+                }
+                Err(e) => {
+                    self.note_flush_failure();
+                    return Err(e).context("Failed to flush batch to QuestDB");
+                }
+            }
+        }
+    }
+
+    /// Counts a failed flush attempt in the metrics and notes the ramp's rate
+    fn note_flush_failure(&self) {
+        if let Some(metrics) = &self.metrics {
+            metrics.flush_failed();
+        }
+        self.note_ramp_failure();
+    }
+
+    /// Logs the ramp's rate at the table's first failed flush, the rate the server broke at
+    fn note_ramp_failure(&self) {
+        if let Some(limiter) = &self.rate_limiter
+            && self.send_settings.ramp.is_some()
+            && let Some(rate) = limiter.first_failure()
+        {
+            warn!(
+                "Table '{}' first failed a flush at a ramp rate of {:.0} rows/sec",
+                self.table_name, rate
+            );
+        }
+    }
+}
+
+/// Whether a failed batch is worth reconnecting for, as opposed to a data or config error
+fn is_connection_error(err: &anyhow::Error) -> bool {
+    err.downcast_ref::<questdb::Error>().is_some_and(|e| {
+        matches!(
+            e.code(),
+            ErrorCode::SocketError | ErrorCode::ServerFlushError | ErrorCode::TlsError
+        )
+    })
+}
+
+/// Message a thread panicked with, as collected from its join handle
+pub fn panic_message(payload: &(dyn Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|message| message.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic payload".to_string())
+}
+
+/// Run-wide options shared by every table blast
+#[derive(Clone)]
+pub struct BlastOptions {
+    pub latency_trace: Option<Arc<LatencyTrace>>,
+    pub completion_webhook: Option<String>,
+    pub pre_flush_hook: Arc<dyn PreFlushHook>,
+    /// Bounds concurrent pgsql connections across all tables
+    pub pgsql_limit: Option<Arc<Semaphore>>,
+    /// Bounds concurrent sender threads across all tables
+    pub sender_limit: Option<Arc<Semaphore>>,
+    /// Set to stop every sender after its current batch, e.g. on Ctrl-C
+    pub shutdown: Arc<AtomicBool>,
+    /// Validate and print each table's DDL and workload, without touching the database
+    pub dry_run: bool,
+    /// Validate each table and check that pgwire and ILP are reachable, without writing
+    pub validate_only: bool,
+    /// Records every sender's progress so a failed run can be resumed
+    pub checkpoint: Option<Arc<CheckpointWriter>>,
+    /// Progress of an earlier run to continue from
+    pub resume: Option<Arc<Checkpoint>>,
+    /// Live counters served on `/metrics` when `metrics_port` is set
+    pub metrics: Option<Arc<Metrics>>,
+    /// Build the senders without probing ILP or preparing the table, as a [`RowSource`]
+    /// does to generate rows locally
+    pub generate_only: bool,
+}
+
+/// Orchestrates the blasting process for a single table, reporting how it went
+pub fn blast_table(
+    table_name: &str,
+    table_config: &Table,
+    connection: &Connection,
+    options: &BlastOptions,
+) -> TableReport {
+    let start = Instant::now();
+    let global_sent_counter = Arc::new(AtomicU64::new(0));
+    let warmup = Arc::new(Warmup::new(table_config.send.parallel_senders));
+
+    let done = Arc::new(AtomicBool::new(false));
+    // A zero interval is rejected by `run_table`
+    let reporter = table_config
+        .send
+        .progress_interval
+        .filter(|interval| !interval.is_zero())
+        .map(|interval| {
+            let table_name = table_name.to_string();
+            let counter = Arc::clone(&global_sent_counter);
+            let warmup = Arc::clone(&warmup);
+            let done = Arc::clone(&done);
+            thread::spawn(move || report_progress(&table_name, interval, &counter, &warmup, &done))
+        });
+
+    let mut estimated_bytes = None;
+    let result = run_table(
+        table_name,
+        table_config,
+        connection,
+        options,
+        &global_sent_counter,
+        &warmup,
+        &mut estimated_bytes,
+    );
+
+    if let Some(reporter) = reporter {
+        done.store(true, Ordering::Relaxed);
+        reporter.thread().unpark();
+        let _ = reporter.join();
+    }
+
+    let (outcome, error) = match result {
+        Ok(outcome) => (outcome, None),
+        Err(e) => (TableOutcome::default(), Some(e.to_string())),
+    };
+    // Rows sent by the resumed run are neither requested nor sent again
+    let resumed_rows = options
+        .resume
+        .as_ref()
+        .map_or(0, |checkpoint| checkpoint.rows_sent(table_name));
+    let mixed_rows: u64 = table_config
+        .mixed
+        .iter()
+        .map(|(_, config)| config.send.tot_rows)
+        .sum();
+    let mut report = TableReport::new(
+        table_name,
+        (table_config.send.tot_rows + mixed_rows).saturating_sub(resumed_rows),
+        global_sent_counter.load(Ordering::Relaxed),
+        estimated_bytes,
+        warmup.measured_from.get().unwrap_or(&start).elapsed(),
+        error,
+    );
+    report.column_stats = outcome.column_stats;
+    report.flush_latency = outcome.flush_latency;
+    report.target_rows_per_sec = table_config.send.target_rate;
+    if let Some(target_rate) = table_config.send.target_rate
+        && report.is_success()
+        && !options.dry_run
+        && !options.validate_only
+    {
+        info!(
+            "Table '{}' achieved {:.0} rows/sec against a target_rate of {} ({:.1}%)",
+            table_name,
+            report.rows_per_sec,
+            target_rate,
+            report.rows_per_sec / target_rate as f64 * 100.0
+        );
+    }
+
+    if let Some(url) = &options.completion_webhook
+        && !options.dry_run
+        && !options.validate_only
+    {
+        webhook::notify(
+            url,
+            &TableSummary {
+                table: table_name,
+                success: report.is_success(),
+                rows_sent: report.rows_sent,
+                elapsed_secs: report.elapsed_secs,
+                error: report.error.clone(),
+            },
+        );
+    }
+
+    report
+}
+
+/// Blasts the tables in parallel on at most `workers` threads named `table-worker-N`, each
+/// taking the next table in config order once its previous one completes. Reports come back
+/// in config order, a panicking table reported as failed without stopping the others.
+pub fn blast_tables(
+    tables: &IndexMap<String, Table>,
+    connection: &Connection,
+    options: &BlastOptions,
+    workers: usize,
+) -> Result<Vec<TableReport>> {
+    let start = Instant::now();
+    let tables: Vec<_> = tables.iter().collect();
+    pool::run(
+        "table-worker",
+        &tables,
+        workers,
+        |(table_name, table_config)| {
+            // Names the table in every log line of the worker blasting it
+            let _span = info_span!("table", name = %table_name).entered();
+            panic::catch_unwind(AssertUnwindSafe(|| {
+                blast_table(table_name, table_config, connection, options)
+            }))
+            .unwrap_or_else(|payload| {
+                TableReport::new(
+                    table_name,
+                    table_config.send.tot_rows,
+                    0,
+                    None,
+                    start.elapsed(),
+                    Some(format!(
+                        "Table '{}' panicked: {}",
+                        table_name,
+                        panic_message(&*payload)
+                    )),
+                )
+            })
+        },
+    )
+}
+
+/// Rows in the next batch, drawn from the inclusive `batch_size` and never above the rows
+/// remaining, so even a u32::MAX batch size can't truncate
+fn random_batch_size((min, max): (u32, u32), rows_remaining: u64, rng: &mut StdRng) -> u32 {
+    rng.random_range(min..=max)
+        .min(u32::try_from(rows_remaining).unwrap_or(u32::MAX))
+}
+
+/// Open-loop pause between batches, drawn from `batch_pause`
+fn random_pause(send_settings: &SendSettings, rng: &mut StdRng) -> Duration {
+    let (min_pause, max_pause) = send_settings.batch_pause;
+    match send_settings.pause_distribution {
+        PauseDistribution::Uniform => Duration::from_nanos(
+            rng.random_range(min_pause.as_nanos()..=max_pause.as_nanos()) as u64,
+        ),
+        PauseDistribution::Exponential { mean } => {
+            let pause: f64 = rng.sample(Exp1);
+            Duration::try_from_secs_f64(pause * mean.as_secs_f64())
+                .unwrap_or(max_pause)
+                .clamp(min_pause, max_pause)
+        }
+    }
+}
+
+/// Share of `rows` sent by one sender, the remainder going to the first senders
+fn rows_for_sender(rows: u64, send: &SendSettings, sender_id: u16) -> u64 {
+    let base_rows = rows / send.parallel_senders as u64;
+    let extra_rows = rows % send.parallel_senders as u64;
+    base_rows + if sender_id < extra_rows as u16 { 1 } else { 0 }
+}
+
+/// Tracks the table's warmup, so throughput is only measured once every sender is past it
+#[derive(Debug)]
+struct Warmup {
+    warming_senders: AtomicU16,
+    /// When the last sender finished its warmup rows
+    measured_from: OnceLock<Instant>,
+}
+
+impl Warmup {
+    fn new(parallel_senders: u16) -> Self {
+        Self {
+            warming_senders: AtomicU16::new(parallel_senders),
+            measured_from: OnceLock::new(),
+        }
+    }
+
+    fn finish_sender(&self) {
+        if self.warming_senders.fetch_sub(1, Ordering::Relaxed) == 1 {
+            let _ = self.measured_from.set(Instant::now());
+        }
+    }
+
+    fn is_done(&self) -> bool {
+        self.measured_from.get().is_some()
+    }
+}
+
+/// Prints what a blast of the table would do, as one block so parallel tables don't interleave
+fn print_plan(table_name: &str, table_config: &Table, total_bytes: Option<u64>) {
+    let send = &table_config.send;
+    let mut plan = format!(
+        "-- Table '{}' ({:?} mode)\n",
+        table_name, table_config.table_mode
+    );
+    match table_ddl(table_name, table_config) {
+        Some(ddl) => plan.push_str(&format!("{}\n", ddl)),
+        None => plan.push_str("-- No DDL, the existing table's schema is checked\n"),
+    }
+    for sender_id in 0..send.parallel_senders {
+        let workload = match send.duration {
+            Some(duration) => format!("runs for {:?}", duration),
+            None if send.is_unbounded() => "runs until interrupted".to_string(),
+            None => format!("{} rows", rows_for_sender(send.tot_rows, send, sender_id)),
+        };
+        plan.push_str(&format!("-- Sender {}: {}\n", sender_id, workload));
+    }
+    match total_bytes {
+        Some(total_bytes) => plan.push_str(&format!(
+            "-- ~{} of ILP in total",
+            estimate::format_bytes(total_bytes)
+        )),
+        None => plan.push_str("-- ILP volume depends on how many rows are sent in time"),
+    }
+    println!("{}", plan);
+}
+
+/// Logs instantaneous and cumulative rows/sec every `interval` until `done` is set
+fn report_progress(
+    table_name: &str,
+    interval: Duration,
+    counter: &AtomicU64,
+    warmup: &Warmup,
+    done: &AtomicBool,
+) {
+    let mut last_rows = 0;
+    let mut last_sample = Instant::now();
+    loop {
+        // Parked rather than slept so the blast can wake the reporter as soon as it ends
+        let deadline = last_sample + interval;
+        while !done.load(Ordering::Relaxed) && Instant::now() < deadline {
+            thread::park_timeout(deadline.saturating_duration_since(Instant::now()));
+        }
+        if done.load(Ordering::Relaxed) {
+            return;
+        }
+
+        let now = Instant::now();
+        let Some(&start) = warmup.measured_from.get() else {
+            info!("Table '{}' warming up", table_name);
+            last_sample = now;
+            continue;
+        };
+        let rows = counter.load(Ordering::Relaxed);
+        // The first sample after warmup measures from its end
+        last_sample = last_sample.max(start);
+        info!(
+            "Table '{}': {:.0} rows/sec now, {:.0} rows/sec overall, {} rows sent",
+            table_name,
+            (rows - last_rows) as f64 / (now - last_sample).as_secs_f64(),
+            rows as f64 / (now - start).as_secs_f64(),
+            rows
+        );
+        last_rows = rows;
+        last_sample = now;
+    }
+}
+
+/// What blasting a table measured besides the rows sent
+#[derive(Default)]
+struct TableOutcome {
+    column_stats: Option<Vec<ColumnStats>>,
+    flush_latency: Option<LatencySummary>,
+}
+
+/// A table's first sender, built without a database for a [`RowGenerator`] to generate its
+/// rows. Rows of any mixed tables are left out.
+pub struct RowSource {
+    sender: TableSender,
+}
+
+impl RowSource {
+    pub fn new(table_name: &str, table_config: &Table) -> Result<Self> {
+        // Only rendered and validated, never connected to
+        let connection = Connection {
+            ilp: vec!["http::addr=localhost:9000;".to_string()],
+            pgsql: String::new(),
+            pgsql_tls: false,
+            pgsql_tls_ca: None,
+            compression: None,
+            http: HttpSettings::default(),
+            username: None,
+            password: None,
+            token: None,
+        };
+        let options = BlastOptions {
+            latency_trace: None,
+            completion_webhook: None,
+            pre_flush_hook: Arc::new(NoopHook),
+            pgsql_limit: None,
+            sender_limit: None,
+            shutdown: Arc::new(AtomicBool::new(false)),
+            dry_run: false,
+            validate_only: false,
+            checkpoint: None,
+            resume: None,
+            metrics: None,
+            generate_only: true,
+        };
+        let prepared = prepare_senders(
+            table_name,
+            table_config,
+            &connection,
+            &options,
+            &Arc::new(AtomicU64::new(0)),
+            &Arc::new(Warmup::new(table_config.send.parallel_senders)),
+            &mut None,
+        )?
+        .context("Table has no senders to generate rows with")?;
+        let sender = prepared
+            .senders
+            .into_iter()
+            .next()
+            .context("Table has no senders to generate rows with")?;
+        Ok(Self { sender })
+    }
+}
+
+/// Generates a table's rows the way its first sender does, into a buffer rather than over
+/// ILP, e.g. to benchmark the row-generation path without a database
+pub struct RowGenerator<'a> {
+    sender: &'a TableSender,
+    ctx: RowContext<'a>,
+    data_gen: DataGenerator,
+    current_timestamp: i64,
+    buffer: Buffer,
+}
+
+impl<'a> RowGenerator<'a> {
+    /// Starts at the sender's first row, with the row context built once as a sender does
+    pub fn new(source: &'a RowSource) -> Result<Self> {
+        let sender = &source.sender;
+        let mut rng = seeded_rng(sender.seed, sender.sender_id.into());
+        let (current_timestamp, data_gen) = sender.start_generator(&mut rng);
+        Ok(Self {
+            sender,
+            ctx: sender.row_context()?,
+            data_gen,
+            current_timestamp,
+            // Version 2 carries every column type, arrays included
+            buffer: Buffer::new(ProtocolVersion::V2),
+        })
+    }
+
+    /// Generates the next `rows` rows in place of the previous ones, returning their ILP
+    pub fn generate(&mut self, rows: u32) -> Result<&[u8]> {
+        self.buffer.clear();
+        for _ in 0..rows {
+            self.sender.write_row(
+                &mut self.buffer,
+                &mut self.data_gen,
+                &mut self.current_timestamp,
+                &mut self.ctx,
+            )?;
+        }
+        Ok(self.buffer.as_bytes())
+    }
+
+    /// Rebuilds the row context, as senders used to for every batch. Only a baseline for
+    /// the benchmarks.
+    #[doc(hidden)]
+    pub fn rebuild_context(&mut self) -> Result<()> {
+        self.ctx = self.sender.row_context()?;
+        Ok(())
+    }
+}
+
+/// A table's senders, built but not yet started, with what's needed to wrap up its run
+struct PreparedTable {
+    senders: Vec<TableSender>,
+    /// Rows in the table before the run, when `verify` is set
+    rows_before: Option<u64>,
+    total_warmup_rows: u64,
+    stats: Option<Arc<StatsCollector>>,
+    latency: Arc<LatencyCollector>,
+}
+
+fn run_table(
+    table_name: &str,
+    table_config: &Table,
+    connection: &Connection,
+    options: &BlastOptions,
+    global_sent_counter: &Arc<AtomicU64>,
+    warmup: &Arc<Warmup>,
+    estimated_bytes: &mut Option<u64>,
+) -> Result<TableOutcome> {
+    info!("Blasting table '{}'", table_name);
+    validate_mixed(table_name, table_config)?;
+
+    let prepared = prepare_senders(
+        table_name,
+        table_config,
+        connection,
+        options,
+        global_sent_counter,
+        warmup,
+        estimated_bytes,
+    )?;
+    // Mixed tables share the table's senders, so its connections, counters and metrics too
+    let member_options = BlastOptions {
+        metrics: None,
+        checkpoint: None,
+        ..options.clone()
+    };
+    let mut members = Vec::with_capacity(table_config.mixed.len());
+    for (member_name, member_config) in &table_config.mixed {
+        info!(
+            "Preparing table '{}' mixed into '{}'",
+            member_name, table_name
+        );
+        let mut member_bytes = None;
+        let member = prepare_senders(
+            member_name,
+            member_config,
+            connection,
+            &member_options,
+            global_sent_counter,
+            warmup,
+            &mut member_bytes,
+        )
+        .with_context(|| format!("Table '{}' mixed into '{}'", member_name, table_name))?;
+        *estimated_bytes = estimated_bytes
+            .zip(member_bytes)
+            .map(|(bytes, more)| bytes + more);
+        members.push((member_name, member));
+    }
+    // Dry runs and validation stop before anything is sent
+    let Some(PreparedTable {
+        mut senders,
+        rows_before,
+        total_warmup_rows,
+        stats,
+        latency,
+    }) = prepared
+    else {
+        return Ok(TableOutcome::default());
+    };
+    let mut member_stats = Vec::new();
+    for (member_name, member) in members {
+        let Some(member) = member else { continue };
+        for (sender, member_sender) in senders.iter_mut().zip(member.senders) {
+            sender.mixed.push(member_sender);
+        }
+        member_stats.extend(member.stats.map(|stats| (member_name, stats)));
+    }
+
+    // Spawn sender threads
+    let sender_count = senders.len();
+    let mut handles = Vec::new();
+    for sender in senders {
+        let sender_id = sender.sender_id;
+        // Spawned only once a slot is free, and the slot is held until the sender finishes
+        let permit = options.sender_limit.as_ref().map(|limit| {
+            limit.try_acquire_owned().unwrap_or_else(|| {
+                info!(
+                    "Table '{}' sender {} waiting for a sender slot",
+                    table_name, sender_id
+                );
+                limit.acquire_owned()
+            })
+        });
+        let thread_name = format!("{}-sender-{}", table_name, sender_id);
+        let spawned = thread::Builder::new()
+            .name(thread_name.clone())
+            .spawn(move || {
+                let _permit = permit;
+                let _active = sender.metrics.as_ref().map(TableMetrics::sender_active);
+                if let Err(e) = sender.run() {
+                    error!("Sender {} failed: {}", sender_id, e);
+                    return Err(e);
+                }
+                Ok(())
+            });
+
+        handles.push((thread_name, spawned));
+    }
 
     // Wait for all senders to complete
     let mut errors = Vec::new();
-    for handle in handles {
-        match handle.join() {
-            Ok(Ok(())) => {}
-            Ok(Err(e)) => errors.push(e),
-            Err(_) => errors.push(anyhow::anyhow!("Thread panicked")),
+    for (thread_name, spawned) in handles {
+        match spawned.map(thread::JoinHandle::join) {
+            Ok(Ok(Ok(()))) => {}
+            Ok(Ok(Err(e))) => errors.push(e),
+            Ok(Err(payload)) => errors.push(anyhow::anyhow!(
+                "Thread '{}' panicked: {}",
+                thread_name,
+                panic_message(&*payload)
+            )),
+            Err(e) => errors.push(anyhow::anyhow!(
+                "Failed to spawn thread '{}': {}",
+                thread_name,
+                e
+            )),
+        }
+    }
+
+    if !errors.is_empty() {
+        // The probe before the DDL catches most of these, but not e.g. a server going down
+        // in between, which would otherwise leave the data silently gone
+        if errors.len() == sender_count
+            && global_sent_counter.load(Ordering::Relaxed) == 0
+            && table_config.table_mode == TableMode::Recreate
+        {
+            error!(
+                "Every sender of table '{}' failed before sending a row, so the table was \
+                 dropped and recreated but left empty{}",
+                table_name,
+                if total_warmup_rows > 0 {
+                    " but for any warmup rows"
+                } else {
+                    ""
+                }
+            );
+        }
+        return Err(anyhow::anyhow!("Some senders failed: {:?}", errors));
+    }
+
+    let final_count = global_sent_counter.load(Ordering::Relaxed);
+    info!(
+        "Completed blast for table '{}', sent {} rows",
+        table_name, final_count
+    );
+
+    // A shut down run is cut short anyway, so don't keep it waiting on the count
+    if let Some(rows_before) = rows_before
+        && !options.shutdown.load(Ordering::Relaxed)
+    {
+        // Warmup rows are stored too, they just don't count toward the throughput
+        let expected = rows_before + total_warmup_rows + final_count;
+        verify_row_count(
+            table_name,
+            table_config,
+            connection,
+            options.pgsql_limit.as_deref(),
+            expected,
+        )?;
+    }
+
+    // Only logged, the report covers the table the others are mixed into
+    for (member_name, stats) in member_stats {
+        stats.summarize(member_name);
+    }
+    Ok(TableOutcome {
+        column_stats: stats.map(|stats| stats.summarize(table_name)),
+        flush_latency: latency.summarize(table_name),
+    })
+}
+
+/// Validates a table and builds its senders, `None` for dry runs and validation, which stop
+/// before anything is created or sent
+fn prepare_senders(
+    table_name: &str,
+    table_config: &Table,
+    connection: &Connection,
+    options: &BlastOptions,
+    global_sent_counter: &Arc<AtomicU64>,
+    warmup: &Arc<Warmup>,
+    estimated_bytes: &mut Option<u64>,
+) -> Result<Option<PreparedTable>> {
+    // Schema files are only read from the config file's tables, not e.g. server requests
+    if table_config.schema_file.is_some() {
+        return Err(anyhow::anyhow!(
+            "schema_file is only supported in the config file, send the schema inline"
+        ));
+    }
+    if table_config.schema.is_empty() {
+        return Err(anyhow::anyhow!(
+            "Table '{}' has no columns, set schema or schema_file",
+            table_name
+        ));
+    }
+    // Validate table and column names at startup
+    validate_names(table_name, table_config)?;
+    validate_column_settings(table_config)?;
+    validate_events(table_config)?;
+    validate_dedup_keys(table_config)?;
+    if let Some(ratio) = table_config.columns_present_ratio
+        && !(0.0..=1.0).contains(&ratio)
+    {
+        return Err(anyhow::anyhow!(
+            "columns_present_ratio must be between 0 and 1, got {}",
+            ratio
+        ));
+    }
+    if table_config.symbol_pool_size == Some(0) {
+        return Err(anyhow::anyhow!("symbol_pool_size must be at least 1"));
+    }
+    if table_config.wal == Some(true) && !table_config.is_partitioned() {
+        return Err(anyhow::anyhow!(
+            "WAL tables must be partitioned, which needs a designated timestamp and a \
+             partition_by other than NONE"
+        ));
+    }
+    table_config.send.validate()?;
+    let symbol_alphabet = resolve_symbol_alphabet(table_config)?;
+    let symbol_file = match &table_config.symbol_file {
+        Some(path) if symbol_alphabet.is_some() => {
+            return Err(anyhow::anyhow!(
+                "symbol_file '{}' cannot be combined with a symbol alphabet or Unicode range",
+                path
+            ));
+        }
+        Some(path) => Some(Arc::new(SymbolFile::load(path)?)),
+        None => None,
+    };
+    // Loaded once and shared by all senders
+    let mut seed_pools: HashMap<&str, Arc<SymbolPool>> = HashMap::new();
+    for (col_name, settings) in &table_config.columns {
+        if let Some(path) = &settings.seed_file {
+            let file = SymbolFile::load(path)
+                .with_context(|| format!("Invalid seed_file of column '{}'", col_name))?;
+            seed_pools.insert(col_name, Arc::new(SymbolPool::from_file(file)));
+        }
+        if let Some(values) = &settings.values {
+            let pool = SymbolPool::from_values(values)
+                .with_context(|| format!("Invalid values of column '{}'", col_name))?;
+            seed_pools.insert(col_name, Arc::new(pool));
+        }
+    }
+    // Pools are built in schema order so a seed reproduces the same values
+    let mut pool_rng = seeded_rng(table_config.seed, SYMBOL_POOL_STREAM);
+    let symbol_pools: HashMap<&str, Arc<SymbolPool>> = table_config
+        .schema
+        .iter()
+        .filter(|(_, col_type)| matches!(col_type, ColType::Symbol))
+        .map(|(col_name, _)| {
+            if let Some(pool) = seed_pools.get(col_name.as_str()) {
+                return Ok((col_name.as_str(), Arc::clone(pool)));
+            }
+            let settings = table_config.columns.get(col_name);
+            let pool = SymbolPool::new(
+                col_name,
+                settings.and_then(|settings| settings.cardinality),
+                table_config.symbol_pool_size.unwrap_or(SYMBOL_POOL_SIZE),
+                settings.and_then(|settings| settings.template.as_ref()),
+                symbol_alphabet.as_deref(),
+                symbol_file.as_deref(),
+                &mut pool_rng,
+            )?;
+            Ok((col_name.as_str(), Arc::new(pool)))
+        })
+        .collect::<Result<_>>()?;
+    let ilp_connections = ilp_conf_strings(connection, table_config)?;
+    validate_auto_flush(table_config, &connection.http)?;
+    let max_symbol_bytes = max_symbol_bytes(table_config, symbol_pools.values());
+    let avg_symbol_bytes = avg_symbol_bytes(symbol_pools.values());
+    estimate::check_row_size(table_name, table_config, max_symbol_bytes, avg_symbol_bytes)?;
+    // Duration and unbounded runs send as many rows as fit, so there's no volume to
+    // estimate up front
+    let total_bytes = (table_config.send.tot_rows > 0)
+        .then(|| estimate::estimate_total_bytes(table_name, table_config, avg_symbol_bytes));
+    *estimated_bytes = total_bytes;
+
+    // With ordered timestamps, each sender's window fits all of its rows at the largest
+    // per-row increment, so the windows line up back to back without overlapping
+    let max_row_interval = table_config.send.row_interval.1.as_nanos() as i64;
+    let max_row_increment = table_config.send.ts_grid.map_or(max_row_interval, |grid| {
+        max_row_interval.max(grid.as_nanos() as i64)
+    });
+    let mut next_window_start = now_nanos();
+
+    // A historical window is split between the senders the same way, each row advancing by
+    // an equal share of it
+    let window_step = match (table_config.send.ts_start, table_config.send.ts_end) {
+        (Some(start), Some(end)) => {
+            let start = start
+                .timestamp_nanos_opt()
+                .context("ts_start is out of range")?;
+            let end = end
+                .timestamp_nanos_opt()
+                .context("ts_end is out of range")?;
+            // Warmup rows are timestamped within the window too
+            let rows = table_config.send.tot_rows + table_config.send.warmup_rows;
+            let step = (end - start) / rows.max(1) as i64;
+            if step < 1 {
+                return Err(anyhow::anyhow!(
+                    "ts_start must be before ts_end with at least 1ns per row"
+                ));
+            }
+            // Timestamps advance before each row, so the first row lands on ts_start
+            next_window_start = start - step;
+            Some(step)
+        }
+        (None, None) => None,
+        _ => {
+            return Err(anyhow::anyhow!("ts_start and ts_end must be set together"));
+        }
+    };
+
+    // Calculate rows per sender, or run every sender for the whole duration
+    let total_rows = table_config.send.tot_rows;
+    let parallel_senders = table_config.send.parallel_senders;
+    let base_rows_per_sender = total_rows / parallel_senders as u64;
+    let extra_rows = total_rows % parallel_senders as u64;
+
+    match table_config.send.duration {
+        Some(duration) => info!(
+            "Running {} senders for {:?} each",
+            parallel_senders, duration
+        ),
+        None if table_config.send.is_unbounded() => info!(
+            "Running {} senders until interrupted (Ctrl-C)",
+            parallel_senders
+        ),
+        None => info!(
+            "Distributing {} total rows across {} senders ({} base + {} extra)",
+            total_rows, parallel_senders, base_rows_per_sender, extra_rows
+        ),
+    }
+    warn_interleaved_timestamps(table_name, table_config, window_step.is_some());
+
+    let resumed_table = options
+        .resume
+        .as_ref()
+        .and_then(|checkpoint| checkpoint.table(table_name));
+    if let Some(progress) = resumed_table {
+        if table_config.send.duration.is_some() {
+            return Err(anyhow::anyhow!(
+                "Table '{}' runs for a duration, so it can't be resumed from a checkpoint",
+                table_name
+            ));
+        }
+        // Rows are split between senders by their count, so it must not change
+        if progress.senders.len() != usize::from(parallel_senders) {
+            return Err(anyhow::anyhow!(
+                "The checkpoint of table '{}' has {} senders, but the table has {}",
+                table_name,
+                progress.senders.len(),
+                parallel_senders
+            ));
+        }
+        info!(
+            "Resuming table '{}' after {} rows",
+            table_name,
+            options
+                .resume
+                .as_ref()
+                .map_or(0, |checkpoint| checkpoint.rows_sent(table_name))
+        );
+    }
+
+    if options.dry_run {
+        print_plan(table_name, table_config, total_bytes);
+        return Ok(None);
+    }
+    if options.validate_only {
+        check_connectivity(
+            table_name,
+            &ilp_connections,
+            connection,
+            options.pgsql_limit.as_deref(),
+        )?;
+        return Ok(None);
+    }
+
+    let rows_before = if options.generate_only {
+        None
+    } else {
+        probe_ilp(&ilp_connections, connection)?;
+        prepare_table(
+            table_name,
+            table_config,
+            connection,
+            options.pgsql_limit.as_deref(),
+        )?
+    };
+
+    let rate_limiter = match (&table_config.send.ramp, table_config.send.max_rows_per_sec) {
+        (Some(ramp), _) => Some(Arc::new(RateLimiter::ramped(ramp))),
+        (None, Some(rows_per_sec)) => Some(Arc::new(RateLimiter::new(rows_per_sec))),
+        (None, None) => None,
+    };
+
+    // Same order as each sender's field columns
+    let stats = table_config.column_stats.then(|| {
+        let columns = table_config
+            .schema
+            .iter()
+            .filter(|(col_name, col_type)| {
+                !matches!(col_type, ColType::Symbol) && !table_config.is_designated_ts(col_name)
+            })
+            .map(|(col_name, _)| col_name.clone())
+            .collect();
+        Arc::new(StatsCollector::new(columns))
+    });
+    let latency = Arc::new(LatencyCollector::new());
+    let table_metrics = options
+        .metrics
+        .as_ref()
+        .map(|metrics| metrics.register(table_name, global_sent_counter));
+
+    let mut senders = Vec::with_capacity(parallel_senders.into());
+    let mut total_warmup_rows = 0;
+    for sender_id in 0..parallel_senders {
+        let mut rows_for_this_sender =
+            rows_for_sender(table_config.send.tot_rows, &table_config.send, sender_id);
+        let mut warmup_rows =
+            rows_for_sender(table_config.send.warmup_rows, &table_config.send, sender_id);
+        let mut start_timestamp = None;
+        if table_config.send.ordered_timestamps || window_step.is_some() {
+            start_timestamp = Some(next_window_start);
+            next_window_start = i64::try_from(rows_for_this_sender + warmup_rows)
+                .ok()
+                .and_then(|rows| rows.checked_mul(window_step.unwrap_or(max_row_increment)))
+                .and_then(|window| next_window_start.checked_add(window))
+                .with_context(|| {
+                    format!(
+                        "Timestamp window of sender {} is out of range, lower tot_rows, \
+                         row_interval or ts_grid",
+                        sender_id
+                    )
+                })?;
+        }
+        // A resumed sender skips its warmup and continues right after its last row, the
+        // windows above are still laid out for the full run
+        let resumed = resumed_table.and_then(|table| table.senders[usize::from(sender_id)].clone());
+        if let Some(resumed) = &resumed {
+            rows_for_this_sender = rows_for_this_sender.saturating_sub(resumed.rows_sent);
+            warmup_rows = 0;
+            start_timestamp = Some(resumed.timestamp);
+        }
+        total_warmup_rows += warmup_rows;
+
+        // Pre-sort columns for efficient ILP serialization
+        let mut symbol_columns = Vec::new();
+        let mut column_pools = Vec::new();
+        let mut field_columns = Vec::new();
+
+        for (col_name, col_type) in &table_config.schema {
+            if table_config.is_designated_ts(col_name) {
+                // Designated timestamp is handled separately
+                continue;
+            }
+
+            match col_type {
+                ColType::Symbol => {
+                    symbol_columns.push(SymbolColumn {
+                        name: col_name.clone(),
+                        null_rate: table_config
+                            .columns
+                            .get(col_name)
+                            .map_or(0.0, |settings| settings.null_rate),
+                    });
+                    column_pools.push(Arc::clone(&symbol_pools[col_name.as_str()]));
+                }
+                ColType::Long
+                | ColType::Int
+                | ColType::Short
+                | ColType::Byte
+                | ColType::Float
+                | ColType::Double
+                | ColType::Timestamp
+                | ColType::Date
+                | ColType::Varchar
+                | ColType::Char
+                | ColType::Ipv4
+                | ColType::Long256
+                | ColType::GeoHash { .. }
+                | ColType::DoubleArray => {
+                    let settings = table_config
+                        .columns
+                        .get(col_name)
+                        .cloned()
+                        .unwrap_or_default();
+                    let int_range = match (settings.range, col_type) {
+                        (Some(range), _) => range,
+                        (None, ColType::Long) => DEFAULT_LONG_RANGE,
+                        (None, col_type) => integer_bounds(col_type).unwrap_or(DEFAULT_LONG_RANGE),
+                    };
+                    let sampler_size = match col_type {
+                        ColType::Float | ColType::Double | ColType::DoubleArray => {
+                            DOUBLE_RANGE.1 - DOUBLE_RANGE.0
+                        }
+                        _ => int_range.1 as f64 - int_range.0 as f64 + 1.0,
+                    };
+                    let sampler = ValueSampler::new(&settings.distribution, sampler_size)
+                        .with_context(|| format!("Column '{}'", col_name))?;
+                    // Striped senders take every n-th value, each offset by its own id
+                    let counter = (settings.generator == Generator::Sequence).then(|| {
+                        let start = settings.start.unwrap_or(0);
+                        let step = settings.step.unwrap_or(1);
+                        if settings.striped {
+                            let senders = i64::from(table_config.send.parallel_senders);
+                            (
+                                start.wrapping_add(i64::from(sender_id).wrapping_mul(step)),
+                                step.wrapping_mul(senders),
+                            )
+                        } else {
+                            (start, step)
+                        }
+                    });
+                    field_columns.push(FieldColumn {
+                        name: col_name.clone(),
+                        col_type: col_type.clone(),
+                        settings,
+                        sequence_symbol: None,
+                        counter,
+                        int_range,
+                        sampler,
+                        value_pool: seed_pools.get(col_name.as_str()).cloned(),
+                        derive_source: None,
+                    });
+                }
+            }
+        }
+
+        let event_symbol = table_config.events.as_ref().and_then(|events| {
+            symbol_columns
+                .iter()
+                .position(|column| column.name == events.id_column)
+                .map(|idx| (idx, events.size))
+        });
+
+        // Resolved after sorting, since a sequence may precede its symbol column in the schema
+        for column in &mut field_columns {
+            column.sequence_symbol = column.settings.sequence_of.as_ref().and_then(|symbol| {
+                symbol_columns
+                    .iter()
+                    .position(|column| &column.name == symbol)
+            });
+        }
+        let field_names: Vec<String> = field_columns
+            .iter()
+            .map(|column| column.name.clone())
+            .collect();
+        for column in &mut field_columns {
+            column.derive_source = column
+                .settings
+                .derive
+                .as_ref()
+                .and_then(|derive| field_names.iter().position(|name| name == derive.source()));
+        }
+
+        let sender = TableSender {
+            sender_id,
+            table_name: table_name.to_string(),
+            ilp_table_name: table_config
+                .ilp_table_name
+                .as_deref()
+                .unwrap_or(table_name)
+                .to_string(),
+            send_settings: table_config.send.clone(),
+            ilp_connection: ilp_connections[sender_id as usize % ilp_connections.len()].clone(),
+            rows_to_send: (table_config.send.tot_rows > 0).then_some(rows_for_this_sender),
+            global_sent_counter: Arc::clone(global_sent_counter),
+            warmup: Arc::clone(warmup),
+            warmup_rows,
+            options: options.clone(),
+            rate_limiter: rate_limiter.clone(),
+            symbol_pools: column_pools,
+            event_symbol,
+            columns_present_ratio: table_config.columns_present_ratio,
+            seed: table_config.seed,
+            partition_by: table_config.partition_by,
+            has_designated_ts: table_config.designated_ts.is_some(),
+            ts_precision: table_config.ts_precision,
+            start_timestamp,
+            fixed_increment: window_step,
+            stats: stats.clone(),
+            resumed,
+            flush_latencies: RefCell::new(latency::new_histogram()),
+            latency: Arc::clone(&latency),
+            metrics: table_metrics.clone(),
+            mixed: Vec::new(),
+            mixed_state: RefCell::new(Vec::new()),
+            symbol_columns,
+            field_columns,
+            auto_flush_rows: connection.http.auto_flush_rows,
+            auto_flush_interval: connection.http.auto_flush_interval,
+        };
+
+        match table_config.send.duration {
+            Some(duration) => info!("Starting sender {} for {:?}", sender_id, duration),
+            None if table_config.send.is_unbounded() => {
+                info!("Starting sender {} until interrupted", sender_id)
+            }
+            None => info!(
+                "Starting sender {} with {} rows to send",
+                sender_id, rows_for_this_sender
+            ),
+        }
+
+        senders.push(sender);
+    }
+
+    Ok(Some(PreparedTable {
+        senders,
+        rows_before,
+        total_warmup_rows,
+        stats,
+        latency,
+    }))
+}
+
+/// Checks the HTTP auto-flush against the table's own flushing. It can't be combined with
+/// `flush_bytes_threshold`, and warns when it would never flush before the batch does.
+fn validate_auto_flush(table_config: &Table, http: &HttpSettings) -> Result<()> {
+    let auto_flush = http.auto_flush_rows.is_some() || http.auto_flush_interval.is_some();
+    if auto_flush && table_config.send.flush_bytes_threshold.is_some() {
+        return Err(anyhow::anyhow!(
+            "flush_bytes_threshold can't be combined with the HTTP auto_flush_rows or \
+             auto_flush_interval, choose one way to flush mid-batch"
+        ));
+    }
+    let max_batch_size = table_config.send.batch_size.1;
+    if let Some(rows) = http.auto_flush_rows
+        && rows >= max_batch_size as usize
+    {
+        warn!(
+            "auto_flush_rows of {} is not below the max batch_size of {}, every batch is \
+             flushed whole anyway",
+            rows, max_batch_size
+        );
+    }
+    Ok(())
+}
+
+/// Checks that the tables mixed into this one can share its senders. Their rows are
+/// counted with the table's and fail with its batches, so nothing can be told apart per
+/// table or retried on a new connection.
+fn validate_mixed(table_name: &str, table_config: &Table) -> Result<()> {
+    if table_config.mixed_with.is_empty() {
+        return Ok(());
+    }
+    // Only a config file's tables can be mixed, not those of a single blast request
+    if table_config.mixed.len() != table_config.mixed_with.len() {
+        return Err(anyhow::anyhow!(
+            "Table '{}' is mixed with tables that aren't in the same config",
+            table_name
+        ));
+    }
+    if table_config.send.max_reconnects > 0 {
+        return Err(anyhow::anyhow!(
+            "Table '{}' is mixed with other tables, so it can't set max_reconnects",
+            table_name
+        ));
+    }
+    let group = std::iter::once((table_name, table_config)).chain(
+        table_config
+            .mixed
+            .iter()
+            .map(|(name, config)| (name.as_str(), config)),
+    );
+    for (name, config) in group {
+        if config.send.duration.is_some() || config.send.warmup_rows > 0 || config.verify {
+            return Err(anyhow::anyhow!(
+                "Table '{}' is mixed with other tables, so it can't set a duration, \
+                 warmup_rows or verify",
+                name
+            ));
+        }
+        if config.send.parallel_senders != table_config.send.parallel_senders {
+            return Err(anyhow::anyhow!(
+                "Table '{}' must have the same parallel_senders as '{}', which it is mixed into",
+                name,
+                table_name
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Warns when several senders start within a second of each other and interleave their
+/// designated timestamps, which QuestDB ingests out of order (O3) whether or not that was
+/// meant to be benchmarked
+fn warn_interleaved_timestamps(table_name: &str, table_config: &Table, has_window: bool) {
+    let send = &table_config.send;
+    // Ordered or windowed senders each get their own range, an `out_of_order_rate` already
+    // asks for O3, and wall-clock senders all write around now
+    if table_config.designated_ts.is_none()
+        || send.parallel_senders < 2
+        || send.ordered_timestamps
+        || has_window
+        || send.out_of_order_rate > 0.0
+        || send.ts_mode == TsMode::Wallclock
+    {
+        return;
+    }
+    warn!(
+        "Table '{}' has {} senders writing interleaved designated timestamps, so QuestDB will \
+         ingest its rows out of order (O3); set `ordered_timestamps` to give each sender its \
+         own range",
+        table_name, send.parallel_senders
+    );
+
+    // Each sender's range spans its rows at the mean row interval, and starts up to 1s after
+    // now. For start offsets d uniform in [0, 1s), two ranges of span s seconds overlap on
+    // average by s - E|d| = s - 1/3 when s >= 1, or by s^2 - s^3/3 otherwise.
+    if send.duration.is_none() {
+        let rows = (send.tot_rows + send.warmup_rows) / u64::from(send.parallel_senders);
+        let mean_interval = (send.row_interval.0 + send.row_interval.1).as_secs_f64() / 2.0;
+        let span = rows as f64 * mean_interval;
+        if span > 0.0 {
+            let overlap = if span >= 1.0 {
+                1.0 - 1.0 / (3.0 * span)
+            } else {
+                span - span * span / 3.0
+            };
+            info!(
+                "Table '{}' senders each span about {:.3}s of timestamps, so any two of them \
+                 overlap by about {:.0}% of their range",
+                table_name,
+                span,
+                overlap * 100.0
+            );
+        }
+    }
+}
+
+/// Validates all table and column names at startup to ensure they're valid for QuestDB ILP
+fn validate_names(table_name: &str, table_config: &Table) -> Result<()> {
+    // Validate table name
+    TableName::new(table_name).with_context(|| format!("Invalid table name: '{}'", table_name))?;
+    if let Some(ilp_table_name) = &table_config.ilp_table_name {
+        TableName::new(ilp_table_name)
+            .with_context(|| format!("Invalid ILP table name: '{}'", ilp_table_name))?;
+    }
+
+    // Validate all column names in schema
+    for (col_name, _) in &table_config.schema {
+        ColumnName::new(col_name.as_str())
+            .with_context(|| format!("Invalid column name: '{}'", col_name))?;
+    }
+
+    // QuestDB column names are case-insensitive, and a duplicate would only fail the CREATE
+    // TABLE after the old table was already dropped
+    let mut seen = HashSet::new();
+    let mut duplicates = Vec::new();
+    for (col_name, _) in &table_config.schema {
+        if !seen.insert(col_name.to_lowercase()) && !duplicates.contains(col_name) {
+            duplicates.push(col_name.clone());
+        }
+    }
+    if !duplicates.is_empty() {
+        return Err(anyhow::anyhow!(
+            "Duplicate column names in the schema of table '{}': {}",
+            table_name,
+            duplicates.join(", ")
+        ));
+    }
+
+    // Validate designated timestamp column name
+    if let Some(designated_ts) = &table_config.designated_ts {
+        ColumnName::new(designated_ts.as_str()).with_context(|| {
+            format!(
+                "Invalid designated timestamp column name: '{}'",
+                designated_ts
+            )
+        })?;
+        match table_config
+            .schema
+            .iter()
+            .find(|(col_name, _)| col_name == designated_ts)
+        {
+            Some((_, ColType::Timestamp)) => {}
+            Some((_, col_type)) => {
+                return Err(anyhow::anyhow!(
+                    "Designated timestamp '{}' is a {:?} column, it must be a Timestamp",
+                    designated_ts,
+                    col_type
+                ));
+            }
+            None => {
+                return Err(anyhow::anyhow!(
+                    "Designated timestamp '{}' is not in the schema",
+                    designated_ts
+                ));
+            }
+        }
+    }
+
+    info!("All table and column names validated successfully");
+    Ok(())
+}
+
+/// Validates that per-column settings refer to schema columns and suit their types
+fn validate_column_settings(table_config: &Table) -> Result<()> {
+    for (col_name, settings) in &table_config.columns {
+        let col_type = table_config
+            .schema
+            .iter()
+            .find(|(name, _)| name == col_name)
+            .map(|(_, col_type)| col_type)
+            .with_context(|| format!("Column settings for unknown column '{}'", col_name))?;
+
+        if settings.drift != 0.0
+            && !matches!(
+                col_type,
+                ColType::Long
+                    | ColType::Int
+                    | ColType::Short
+                    | ColType::Byte
+                    | ColType::Float
+                    | ColType::Double
+            )
+        {
+            return Err(anyhow::anyhow!(
+                "Column '{}' has drift but is not a numeric column",
+                col_name
+            ));
+        }
+
+        if !(0.0..=1.0).contains(&settings.null_rate) {
+            return Err(anyhow::anyhow!(
+                "null_rate of column '{}' must be between 0 and 1, got {}",
+                col_name,
+                settings.null_rate
+            ));
+        }
+        let is_event_id = table_config
+            .events
+            .as_ref()
+            .is_some_and(|events| &events.id_column == col_name);
+        if settings.null_rate > 0.0 && (table_config.is_designated_ts(col_name) || is_event_id) {
+            return Err(anyhow::anyhow!(
+                "Column '{}' cannot have a null_rate, it is the designated timestamp or event id",
+                col_name
+            ));
+        }
+
+        if let Some(cidr) = settings.cidr {
+            if !matches!(col_type, ColType::Ipv4) {
+                return Err(anyhow::anyhow!(
+                    "Column '{}' has a cidr but is not an Ipv4 column",
+                    col_name
+                ));
+            }
+            if cidr.prefix_len == 32 && cidr.network == 0 {
+                return Err(anyhow::anyhow!(
+                    "Column '{}' has the cidr 0.0.0.0/32, which only holds QuestDB's null address",
+                    col_name
+                ));
+            }
+        }
+
+        if let Some((min, max)) = settings.offset {
+            if !matches!(col_type, ColType::Timestamp | ColType::Date) {
+                return Err(anyhow::anyhow!(
+                    "Column '{}' has an offset but is not a Timestamp or Date column",
+                    col_name
+                ));
+            }
+            if min > max {
+                return Err(anyhow::anyhow!(
+                    "Invalid offset for column '{}': {}ns is after {}ns",
+                    col_name,
+                    min,
+                    max
+                ));
+            }
+        }
+
+        if let Some((min, max)) = settings.array_len {
+            if !matches!(col_type, ColType::DoubleArray) {
+                return Err(anyhow::anyhow!(
+                    "Column '{}' has an array_len but is not a DoubleArray column",
+                    col_name
+                ));
+            }
+            if min > max {
+                return Err(anyhow::anyhow!(
+                    "Invalid array_len [{}, {}] for column '{}'",
+                    min,
+                    max,
+                    col_name
+                ));
+            }
+        }
+
+        if settings.values.is_some() {
+            if !matches!(col_type, ColType::Symbol | ColType::Varchar) {
+                return Err(anyhow::anyhow!(
+                    "Column '{}' has values but is not a Symbol or Varchar column",
+                    col_name
+                ));
+            }
+            if settings.seed_file.is_some()
+                || settings.cardinality.is_some()
+                || settings.template.is_some()
+            {
+                return Err(anyhow::anyhow!(
+                    "Column '{}' has explicit values, which cannot be combined with a \
+                     seed_file, cardinality or template",
+                    col_name
+                ));
+            }
+        }
+
+        if let Some(path) = &settings.seed_file {
+            if !matches!(col_type, ColType::Symbol | ColType::Varchar) {
+                return Err(anyhow::anyhow!(
+                    "Column '{}' has a seed_file but is not a Symbol or Varchar column",
+                    col_name
+                ));
+            }
+            if settings.cardinality.is_some() || settings.template.is_some() {
+                return Err(anyhow::anyhow!(
+                    "Column '{}' has a seed_file '{}', which cannot be combined with a \
+                     cardinality or template",
+                    col_name,
+                    path
+                ));
+            }
+        }
+
+        if settings.template.is_some() {
+            if !matches!(col_type, ColType::Symbol) {
+                return Err(anyhow::anyhow!(
+                    "Column '{}' has a template but is not a Symbol column",
+                    col_name
+                ));
+            }
+            if table_config.symbol_file.is_some() {
+                return Err(anyhow::anyhow!(
+                    "Column '{}' has a template, which cannot be combined with symbol_file",
+                    col_name
+                ));
+            }
+        }
+
+        if let Some(cardinality) = settings.cardinality {
+            if !matches!(col_type, ColType::Symbol) {
+                return Err(anyhow::anyhow!(
+                    "Column '{}' has a cardinality but is not a Symbol column",
+                    col_name
+                ));
+            }
+            if cardinality == 0 {
+                return Err(anyhow::anyhow!(
+                    "Cardinality of column '{}' must be at least 1",
+                    col_name
+                ));
+            }
+            if table_config.symbol_file.is_some() {
+                return Err(anyhow::anyhow!(
+                    "Column '{}' has a cardinality, which cannot be combined with symbol_file",
+                    col_name
+                ));
+            }
+        }
+
+        if !matches!(settings.distribution, ValueDistribution::Uniform)
+            && !matches!(
+                col_type,
+                ColType::Long
+                    | ColType::Int
+                    | ColType::Short
+                    | ColType::Byte
+                    | ColType::Float
+                    | ColType::Double
+                    | ColType::DoubleArray
+            )
+        {
+            return Err(anyhow::anyhow!(
+                "Column '{}' has a distribution but is not a numeric column",
+                col_name
+            ));
+        }
+
+        if let Some((min, max)) = settings.range {
+            let Some((type_min, type_max)) = integer_bounds(col_type) else {
+                return Err(anyhow::anyhow!(
+                    "Column '{}' has a range but is not an integer column",
+                    col_name
+                ));
+            };
+            if min > max || min < type_min || max > type_max {
+                return Err(anyhow::anyhow!(
+                    "Invalid range [{}, {}] for column '{}', must be ordered and within [{}, {}]",
+                    min,
+                    max,
+                    col_name,
+                    type_min,
+                    type_max
+                ));
+            }
+        }
+
+        if settings.generator == Generator::Sequence {
+            if !matches!(col_type, ColType::Long) {
+                return Err(anyhow::anyhow!(
+                    "Column '{}' is a sequence but is not a Long column",
+                    col_name
+                ));
+            }
+            if settings.sequence_of.is_some() {
+                return Err(anyhow::anyhow!(
+                    "Column '{}' cannot be both a sequence and a per-symbol sequence",
+                    col_name
+                ));
+            }
+            if settings.step == Some(0) {
+                return Err(anyhow::anyhow!(
+                    "Column '{}' has a sequence step of 0",
+                    col_name
+                ));
+            }
+        } else if settings.start.is_some() || settings.step.is_some() || settings.striped {
+            return Err(anyhow::anyhow!(
+                "Column '{}' sets start, step or striped without generator = \"sequence\"",
+                col_name
+            ));
+        }
+
+        if let Some(symbol) = &settings.sequence_of {
+            if let Some(events) = &table_config.events
+                && &events.id_column == symbol
+            {
+                return Err(anyhow::anyhow!(
+                    "Column '{}' cannot be a sequence of the event id column '{}'",
+                    col_name,
+                    symbol
+                ));
+            }
+            if !matches!(col_type, ColType::Long) {
+                return Err(anyhow::anyhow!(
+                    "Column '{}' is a per-symbol sequence but is not a Long column",
+                    col_name
+                ));
+            }
+            let is_symbol_column = table_config
+                .schema
+                .iter()
+                .any(|(name, col_type)| name == symbol && matches!(col_type, ColType::Symbol));
+            if !is_symbol_column {
+                return Err(anyhow::anyhow!(
+                    "Column '{}' is a sequence of '{}', which is not a Symbol column",
+                    col_name,
+                    symbol
+                ));
+            }
+        }
+
+        if let Some(derive) = &settings.derive {
+            validate_derivation(table_config, col_name, col_type, settings, derive)?;
+        }
+    }
+    Ok(())
+}
+
+/// Validates that a derived column reads a numeric column generated before it in the row
+fn validate_derivation(
+    table_config: &Table,
+    col_name: &str,
+    col_type: &ColType,
+    settings: &ColumnSettings,
+    derive: &Derivation,
+) -> Result<()> {
+    if settings.generator == Generator::Sequence || settings.sequence_of.is_some() {
+        return Err(anyhow::anyhow!(
+            "Column '{}' cannot be both derived and a sequence",
+            col_name
+        ));
+    }
+    let source = derive.source();
+    let position = |name: &str| table_config.schema.iter().position(|(col, _)| col == name);
+    let Some(source_idx) = position(source) else {
+        return Err(anyhow::anyhow!(
+            "Column '{}' is derived from unknown column '{}'",
+            col_name,
+            source
+        ));
+    };
+    // Columns are generated in schema order, so the source's value is known by then
+    if position(col_name).is_some_and(|idx| idx <= source_idx) {
+        return Err(anyhow::anyhow!(
+            "Column '{}' is derived from '{}', which must come before it in the schema",
+            col_name,
+            source
+        ));
+    }
+    let source_numeric = matches!(
+        table_config.schema[source_idx].1,
+        ColType::Long
+            | ColType::Int
+            | ColType::Short
+            | ColType::Byte
+            | ColType::Float
+            | ColType::Double
+    );
+    if !source_numeric || table_config.is_designated_ts(source) {
+        return Err(anyhow::anyhow!(
+            "Column '{}' is derived from '{}', which is not a numeric column",
+            col_name,
+            source
+        ));
+    }
+
+    match derive {
+        Derivation::Linear {
+            factor,
+            offset,
+            jitter,
+            ..
+        } => {
+            let numeric = matches!(
+                col_type,
+                ColType::Long
+                    | ColType::Int
+                    | ColType::Short
+                    | ColType::Byte
+                    | ColType::Float
+                    | ColType::Double
+            );
+            if !numeric {
+                return Err(anyhow::anyhow!(
+                    "Column '{}' has a linear derivation but is not a numeric column",
+                    col_name
+                ));
+            }
+            if !(factor.is_finite() && offset.is_finite() && jitter.is_finite() && *jitter >= 0.0) {
+                return Err(anyhow::anyhow!(
+                    "Linear derivation of column '{}' needs a finite factor and offset and a \
+                     non-negative jitter",
+                    col_name
+                ));
+            }
+        }
+        Derivation::Buckets {
+            thresholds, values, ..
+        } => {
+            if !matches!(col_type, ColType::Varchar) {
+                return Err(anyhow::anyhow!(
+                    "Column '{}' has a buckets derivation but is not a Varchar column",
+                    col_name
+                ));
+            }
+            if values.len() != thresholds.len() + 1 {
+                return Err(anyhow::anyhow!(
+                    "Buckets derivation of column '{}' needs one more value than thresholds, \
+                     got {} values for {} thresholds",
+                    col_name,
+                    values.len(),
+                    thresholds.len()
+                ));
+            }
+            if !thresholds.is_sorted_by(|a, b| a < b) {
+                return Err(anyhow::anyhow!(
+                    "Bucket thresholds of column '{}' must be strictly increasing",
+                    col_name
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Validates that events group rows by a Symbol column with a sensible size range
+fn validate_events(table_config: &Table) -> Result<()> {
+    let Some(events) = &table_config.events else {
+        return Ok(());
+    };
+
+    let is_symbol_column = table_config
+        .schema
+        .iter()
+        .any(|(name, col_type)| name == &events.id_column && matches!(col_type, ColType::Symbol));
+    if !is_symbol_column {
+        return Err(anyhow::anyhow!(
+            "Event id column '{}' is not a Symbol column",
+            events.id_column
+        ));
+    }
+
+    let (min, max) = events.size;
+    if min == 0 || min > max {
+        return Err(anyhow::anyhow!(
+            "Invalid event size [{}, {}]: min must be at least 1 and not above max",
+            min,
+            max
+        ));
+    }
+    Ok(())
+}
+
+fn validate_dedup_keys(table_config: &Table) -> Result<()> {
+    if table_config.dedup_keys.is_empty() {
+        return Ok(());
+    }
+    if table_config.wal == Some(false) || !table_config.is_partitioned() {
+        return Err(anyhow::anyhow!(
+            "dedup_keys require a WAL table, which must have a designated timestamp, be \
+             partitioned and not use wal = false"
+        ));
+    }
+
+    for key in &table_config.dedup_keys {
+        match table_config.schema.iter().find(|(name, _)| name == key) {
+            Some((_, ColType::Symbol | ColType::Timestamp)) => {}
+            Some((_, col_type)) => {
+                return Err(anyhow::anyhow!(
+                    "Dedup key '{}' is a {:?} column, only Symbol and Timestamp columns are supported",
+                    key,
+                    col_type
+                ));
+            }
+            None => {
+                return Err(anyhow::anyhow!("Dedup key '{}' is not in the schema", key));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Mean length, in bytes, of the values the symbol columns pick from
+fn avg_symbol_bytes<'a>(symbol_pools: impl Iterator<Item = &'a Arc<SymbolPool>>) -> usize {
+    let (total, count) = symbol_pools
+        .flat_map(|pool| pool.values.iter().map(String::len))
+        .fold((0, 0), |(total, count), len| (total + len, count + 1));
+    total.checked_div(count).unwrap_or(0)
+}
+
+/// Longest value, in bytes, that any symbol column of the table can generate
+fn max_symbol_bytes<'a>(
+    table_config: &Table,
+    symbol_pools: impl Iterator<Item = &'a Arc<SymbolPool>>,
+) -> usize {
+    let pool_max = symbol_pools
+        .flat_map(|pool| pool.values.iter().map(String::len))
+        .max()
+        .unwrap_or(0);
+
+    // "evt-<sender id>-<event number>"
+    let event_max = format!("evt-{}-{}", u16::MAX, u64::MAX).len();
+    if table_config.events.is_some() {
+        pool_max.max(event_max)
+    } else {
+        pool_max
+    }
+}
+
+/// Renders the conf string of every ILP endpoint, senders take them round-robin
+fn ilp_conf_strings(connection: &Connection, table_config: &Table) -> Result<Vec<String>> {
+    // Mixed tables are sent over the table's connections
+    let arrays = iter::once(table_config)
+        .chain(table_config.mixed.iter().map(|(_, member)| member))
+        .flat_map(|table| &table.schema)
+        .any(|(_, col_type)| matches!(col_type, ColType::DoubleArray));
+    if connection.ilp.is_empty() {
+        return Err(anyhow::anyhow!("At least one ILP conf string is required"));
+    }
+    connection
+        .ilp
+        .iter()
+        .map(|ilp| ilp_conf_string(connection, ilp, arrays))
+        .collect()
+}
+
+/// Conf string keys of the client's auto-flush, which the client doesn't implement; the
+/// blaster flushes every batch, or mid-batch with the `[database.http]` auto-flush
+const AUTO_FLUSH_KEYS: [&str; 4] = [
+    "auto_flush",
+    "auto_flush_rows",
+    "auto_flush_bytes",
+    "auto_flush_interval",
+];
+
+/// Validates the ILP protocol and renders the conf string, appending the optional
+/// transport settings. Both protocols flush once per generated batch. With `arrays`, the
+/// conf string asks for protocol version 2, the first to carry DoubleArray values.
+fn ilp_conf_string(connection: &Connection, ilp: &str, arrays: bool) -> Result<String> {
+    let protocol = IlpProtocol::parse(ilp)?;
+    debug!("Using ILP protocol {:?}", protocol);
+    // Expanded only here, so the resolved secrets are never logged
+    let mut conf = settings::expand_env(ilp.trim()).context("Invalid ILP conf string")?;
+    if !conf.ends_with(';') {
+        conf.push(';');
+    }
+
+    let params: Vec<(&str, &str)> = conf
+        .split(';')
+        .filter_map(|param| param.split_once('='))
+        .map(|(key, value)| (key.trim(), value.trim()))
+        .collect();
+    for &(key, value) in &params {
+        // "auto_flush=off" is what the blaster needs anyway
+        if AUTO_FLUSH_KEYS.contains(&key) && (key, value) != ("auto_flush", "off") {
+            return Err(anyhow::anyhow!(
+                "'{}' in the ILP conf string conflicts with the blaster's batching, which \
+                 flushes every batch; set auto_flush_rows or auto_flush_interval under \
+                 [database.http] instead",
+                key
+            ));
         }
     }
 
-    if !errors.is_empty() {
-        return Err(anyhow::anyhow!("Some senders failed: {:?}", errors));
+    let http = &connection.http;
+    let mut extra_params = Vec::new();
+    if let Some(compression) = &connection.compression {
+        extra_params.push(("compression", compression.clone()));
+    }
+    if let Some(timeout) = http.request_timeout {
+        extra_params.push(("request_timeout", timeout.as_millis().to_string()));
+    }
+    if let Some(throughput) = http.request_min_throughput {
+        extra_params.push(("request_min_throughput", throughput.to_string()));
+    }
+    if let Some(timeout) = http.retry_timeout {
+        extra_params.push(("retry_timeout", timeout.as_millis().to_string()));
     }
 
-    let final_count = global_sent_counter.load(Ordering::Relaxed);
+    let mut rendered = conf.clone();
+    for (key, value) in extra_params {
+        if !protocol.is_http() {
+            return Err(anyhow::anyhow!(
+                "ILP {} is only supported over HTTP, but the conf string uses {:?}",
+                key,
+                protocol
+            ));
+        }
+        if params.iter().any(|&(existing, _)| existing == key) {
+            return Err(anyhow::anyhow!(
+                "ILP {} is set both in the conf string and in the connection settings",
+                key
+            ));
+        }
+        rendered.push_str(&format!("{}={};", key, value));
+        info!("ILP {} set to {}", key, value);
+    }
+
+    if arrays {
+        match params.iter().find(|&&(key, _)| key == "protocol_version") {
+            // HTTP negotiates the newest version the server supports, TCP stays at 1
+            Some((_, "2")) => {}
+            Some((_, "auto")) if protocol.is_http() => {}
+            Some((_, version)) => {
+                return Err(anyhow::anyhow!(
+                    "DoubleArray columns need ILP protocol_version=2, but the conf string \
+                     sets protocol_version={}",
+                    version
+                ));
+            }
+            None => {
+                rendered.push_str("protocol_version=2;");
+                info!("ILP protocol_version set to 2 for the DoubleArray columns");
+            }
+        }
+    }
+
+    // The blaster flushes by these itself, so they're checked but never rendered
+    let auto_flush = [
+        ("auto_flush_rows", http.auto_flush_rows.is_some()),
+        ("auto_flush_interval", http.auto_flush_interval.is_some()),
+    ];
+    for (key, set) in auto_flush {
+        if set && !protocol.is_http() {
+            return Err(anyhow::anyhow!(
+                "ILP {} is only supported over HTTP, the TCP senders flush every batch, but \
+                 the conf string uses {:?}",
+                key,
+                protocol
+            ));
+        }
+    }
+    if http.auto_flush_rows == Some(0) {
+        return Err(anyhow::anyhow!("ILP auto_flush_rows must be at least 1"));
+    }
+    if http.auto_flush_interval == Some(Duration::ZERO) {
+        return Err(anyhow::anyhow!(
+            "ILP auto_flush_interval must be greater than zero"
+        ));
+    }
+
+    // Credentials are rendered like the transport settings, but never logged
+    if connection.password.is_some() && connection.username.is_none() {
+        return Err(anyhow::anyhow!("ILP password is set without a username"));
+    }
+    if connection.password.is_some() && connection.token.is_some() {
+        return Err(anyhow::anyhow!(
+            "ILP password and token cannot both be set, use basic auth or a token"
+        ));
+    }
+    if connection.password.is_some() && !protocol.is_http() {
+        return Err(anyhow::anyhow!(
+            "ILP password is only supported over HTTP, but the conf string uses {:?}",
+            protocol
+        ));
+    }
+    let credentials = [
+        ("username", &connection.username),
+        ("password", &connection.password),
+        ("token", &connection.token),
+    ];
+    for (key, value) in credentials {
+        let Some(value) = value else {
+            continue;
+        };
+        if params.iter().any(|&(existing, _)| existing == key) {
+            return Err(anyhow::anyhow!(
+                "ILP {} is set both in the conf string and in the connection settings",
+                key
+            ));
+        }
+        let value = settings::expand_env(value).with_context(|| format!("Invalid ILP {}", key))?;
+        rendered.push_str(&format!("{}={};", key, value));
+    }
+
+    if http.retry_timeout.is_some_and(|timeout| !timeout.is_zero()) {
+        warn!(
+            "The client retries failed flushes within retry_timeout, on top of any per-table \
+             send.retry attempts"
+        );
+    }
+
+    Ok(rendered)
+}
+
+/// Opens and closes a pgwire connection and every ILP connection, touching no tables
+fn check_connectivity(
+    table_name: &str,
+    ilp_connections: &[String],
+    connection: &Connection,
+    pgsql_limit: Option<&Semaphore>,
+) -> Result<()> {
+    {
+        let _permit = pgsql_permit(table_name, pgsql_limit);
+        connect_pgsql(connection)?;
+    }
+    info!("Table '{}': pgwire is reachable", table_name);
+    probe_ilp(ilp_connections, connection)?;
     info!(
-        "Completed blast for table '{}', sent {} rows",
-        table_name, final_count
+        "Table '{}': {} ILP endpoint(s) reachable",
+        table_name,
+        ilp_connections.len()
     );
+    Ok(())
+}
 
+/// Connects to every ILP endpoint once, so one that's unreachable or rejects the credentials
+/// fails the table before any DDL runs rather than on the first flush
+fn probe_ilp(ilp_connections: &[String], connection: &Connection) -> Result<()> {
+    let has_credentials = connection.username.is_some()
+        || connection.token.is_some()
+        || connection
+            .ilp
+            .iter()
+            .any(|conf| conf.contains("username=") || conf.contains("token="));
+    for (idx, conf) in ilp_connections.iter().enumerate() {
+        let protocol = IlpProtocol::parse(conf)?;
+        let e = match QuestDbSender::from_conf(conf) {
+            // The HTTP client connects lazily, on the first flush
+            Ok(_) if protocol.is_http() => {
+                ping_http(conf, protocol)
+                    .with_context(|| format!("ILP endpoint {} is unreachable over HTTP", idx))?;
+                continue;
+            }
+            Ok(_) => continue,
+            Err(e) => e,
+        };
+        if matches!(e.code(), ErrorCode::AuthError) {
+            let hint = if has_credentials {
+                "check the configured credentials"
+            } else {
+                "set username and password, or token, under [database]"
+            };
+            return Err(anyhow::anyhow!(
+                "ILP endpoint {} rejected the connection ({}), {}",
+                idx,
+                e.msg(),
+                hint
+            ));
+        }
+        return Err(e).with_context(|| format!("Failed to connect to ILP endpoint {}", idx));
+    }
     Ok(())
 }
 
-/// Validates all table and column names at startup to ensure they're valid for QuestDB ILP
-fn validate_names(table_name: &str, table_config: &Table) -> Result<()> {
-    // Validate table name
-    TableName::new(table_name).with_context(|| format!("Invalid table name: '{}'", table_name))?;
+/// Splits an ILP `addr` into its host and port, unbracketing IPv6 hosts like `[::1]:9000`
+fn split_host_port(addr: &str, default_port: u16) -> Result<(&str, u16)> {
+    let parse_port = |port: &str| {
+        port.parse()
+            .with_context(|| format!("Invalid port in ILP addr '{}'", addr))
+    };
+    if let Some(bracketed) = addr.strip_prefix('[') {
+        let (host, rest) = bracketed
+            .split_once(']')
+            .with_context(|| format!("Unclosed '[' in ILP addr '{}'", addr))?;
+        return match rest.strip_prefix(':') {
+            Some(port) => Ok((host, parse_port(port)?)),
+            None if rest.is_empty() => Ok((host, default_port)),
+            None => Err(anyhow::anyhow!("Invalid ILP addr '{}'", addr)),
+        };
+    }
+    match addr.split_once(':') {
+        // More than one colon is a bare IPv6 address, without a port
+        Some((_, rest)) if rest.contains(':') => Ok((addr, default_port)),
+        Some((host, port)) => Ok((host, parse_port(port)?)),
+        None => Ok((addr, default_port)),
+    }
+}
 
-    // Validate all column names in schema
-    for (col_name, _) in &table_config.schema {
-        ColumnName::new(col_name.as_str())
-            .with_context(|| format!("Invalid column name: '{}'", col_name))?;
+/// Checks that the server behind an HTTP ILP conf string answers, with a `GET /ping` in
+/// plain HTTP or just a TCP connection over HTTPS
+fn ping_http(conf: &str, protocol: IlpProtocol) -> Result<()> {
+    const DEFAULT_HTTP_PORT: u16 = 9000;
+    const PING_TIMEOUT: Duration = Duration::from_secs(5);
+    let addr = conf
+        .split(';')
+        .filter_map(|param| param.split_once('='))
+        .find_map(|(key, value)| (key.trim() == "addr").then_some(value.trim()))
+        .context("The ILP conf string has no addr")?;
+    let (host, port) = split_host_port(addr, DEFAULT_HTTP_PORT)?;
+    let socket_addr = (host, port)
+        .to_socket_addrs()
+        .with_context(|| format!("Failed to resolve '{}'", addr))?
+        .next()
+        .with_context(|| format!("'{}' resolved to no address", addr))?;
+    let mut stream = TcpStream::connect_timeout(&socket_addr, PING_TIMEOUT)
+        .with_context(|| format!("Failed to connect to '{}'", addr))?;
+    if protocol == IlpProtocol::Https {
+        return Ok(());
     }
 
-    // Validate designated timestamp column name
-    ColumnName::new(table_config.designated_ts.as_str()).with_context(|| {
+    stream.set_read_timeout(Some(PING_TIMEOUT))?;
+    std::io::Write::write_all(
+        &mut stream,
         format!(
-            "Invalid designated timestamp column name: '{}'",
-            table_config.designated_ts
+            "GET /ping HTTP/1.1\r\nHost: {}\r\nConnection: close\r\n\r\n",
+            addr
         )
-    })?;
+        .as_bytes(),
+    )?;
+    let mut status_line = String::new();
+    BufReader::new(stream).read_line(&mut status_line)?;
+    // QuestDB answers a ping with 204 No Content
+    match status_line.split_whitespace().nth(1) {
+        Some(status) if status.starts_with('2') => Ok(()),
+        _ => Err(anyhow::anyhow!(
+            "'{}' answered GET /ping with '{}', is it QuestDB's HTTP port?",
+            addr,
+            status_line.trim()
+        )),
+    }
+}
 
-    info!("All table and column names validated successfully");
-    Ok(())
+/// Builds the character set for symbol values from the alphabet and/or Unicode range settings
+fn resolve_symbol_alphabet(table_config: &Table) -> Result<Option<Arc<[char]>>> {
+    if table_config.symbol_alphabet.is_none() && table_config.symbol_unicode_range.is_none() {
+        return Ok(None);
+    }
+
+    let mut alphabet: Vec<char> = Vec::new();
+    if let Some(chars) = &table_config.symbol_alphabet {
+        alphabet.extend(chars.chars());
+    }
+    if let Some((start, end)) = table_config.symbol_unicode_range {
+        if start > end {
+            return Err(anyhow::anyhow!(
+                "Invalid symbol_unicode_range: {:?} is after {:?}",
+                start,
+                end
+            ));
+        }
+        alphabet.extend(start..=end);
+    }
+
+    // Control characters and ILP separators would corrupt or be escaped in the line protocol
+    alphabet.retain(|c| !c.is_control() && !matches!(c, ',' | '=' | ' ' | '\\'));
+    alphabet.sort_unstable();
+    alphabet.dedup();
+
+    if alphabet.is_empty() {
+        return Err(anyhow::anyhow!(
+            "Symbol alphabet has no usable characters after removing control and ILP separator characters"
+        ));
+    }
+
+    info!(
+        "Generating symbols from an alphabet of {} characters",
+        alphabet.len()
+    );
+    Ok(Some(alphabet.into()))
 }
 
-/// Drops and recreates the table using the schema configuration
-fn drop_and_create_table(
+/// Opens a pgwire connection, over TLS when `pgsql_tls` is set
+fn connect_pgsql(connection: &Connection) -> Result<Client> {
+    if !connection.pgsql_tls {
+        return Client::connect(&connection.pgsql, NoTls)
+            .context("Failed to connect to PostgreSQL");
+    }
+
+    let mut builder = TlsConnector::builder();
+    if let Some(path) = &connection.pgsql_tls_ca {
+        let pem =
+            fs::read(path).with_context(|| format!("Failed to read pgsql_tls_ca '{}'", path))?;
+        let cert = Certificate::from_pem(&pem)
+            .with_context(|| format!("Invalid PEM certificate in pgsql_tls_ca '{}'", path))?;
+        builder.add_root_certificate(cert);
+    }
+    let connector = builder
+        .build()
+        .context("Failed to set up TLS for PostgreSQL")?;
+    Client::connect(&connection.pgsql, MakeTlsConnector::new(connector)).context(
+        "Failed to connect to PostgreSQL over TLS, check that the server has TLS enabled \
+         and that its certificate is trusted (see pgsql_tls_ca)",
+    )
+}
+
+/// Gets the table ready for ingestion according to its `table_mode`, returning its row
+/// count beforehand when the table is verified
+fn prepare_table(
     table_name: &str,
     table_config: &Table,
-    pgsql_connection: &str,
-) -> Result<()> {
-    info!("Dropping and recreating table '{}'", table_name);
+    connection: &Connection,
+    pgsql_limit: Option<&Semaphore>,
+) -> Result<Option<u64>> {
+    // Held until the client is dropped at the end of this function
+    let _permit = pgsql_permit(table_name, pgsql_limit);
+    let mut client = connect_pgsql(connection)?;
+
+    match table_config.table_mode {
+        TableMode::Recreate => drop_and_create_table(&mut client, table_name, table_config),
+        TableMode::Append => {
+            info!("Appending to existing table '{}'", table_name);
+            check_existing_schema(&mut client, table_name, table_config)
+        }
+        TableMode::CreateIfMissing => {
+            info!("Creating table '{}' unless it exists", table_name);
+            let create_sql = table_ddl(table_name, table_config).unwrap_or_default();
+            debug!("Creating table with SQL: {}", create_sql);
+            client.batch_execute(&create_sql).with_context(|| {
+                format!(
+                    "Failed to create table '{}' with SQL: {}",
+                    table_name, create_sql
+                )
+            })?;
+            check_existing_schema(&mut client, table_name, table_config)
+        }
+    }?;
+
+    if !table_config.verify {
+        return Ok(None);
+    }
+    count_rows(&mut client, table_name).map(Some)
+}
 
-    let mut client =
-        Client::connect(pgsql_connection, NoTls).context("Failed to connect to PostgreSQL")?;
+/// Waits for a free pgsql connection slot, if they are limited
+fn pgsql_permit<'a>(
+    table_name: &str,
+    pgsql_limit: Option<&'a Semaphore>,
+) -> Option<SemaphorePermit<'a>> {
+    pgsql_limit.map(|limit| {
+        limit.try_acquire().unwrap_or_else(|| {
+            info!("Table '{}' waiting for a pgsql connection slot", table_name);
+            limit.acquire()
+        })
+    })
+}
 
-    // Drop table if exists
-    let drop_sql = format!("DROP TABLE IF EXISTS {}", table_name);
+fn count_rows(client: &mut Client, table_name: &str) -> Result<u64> {
+    let sql = format!("SELECT count() FROM {}", quote_ident(table_name));
     client
-        .execute(&drop_sql, &[])
-        .with_context(|| format!("Failed to drop table '{}'", table_name))?;
+        .simple_query(&sql)
+        .with_context(|| format!("Failed to count the rows of table '{}'", table_name))?
+        .iter()
+        .find_map(|message| match message {
+            SimpleQueryMessage::Row(row) => row.get(0).and_then(|count| count.parse().ok()),
+            _ => None,
+        })
+        .with_context(|| format!("No row count returned for table '{}'", table_name))
+}
 
-    // Create table with schema
-    let mut create_sql = format!("CREATE TABLE {} (", table_name);
-    let mut column_defs = Vec::new();
+/// Polls the table's row count until it reaches `expected` or `verify_timeout` passes,
+/// warning if it never does. ILP is applied asynchronously, so the count lags the flushes.
+fn verify_row_count(
+    table_name: &str,
+    table_config: &Table,
+    connection: &Connection,
+    pgsql_limit: Option<&Semaphore>,
+    expected: u64,
+) -> Result<()> {
+    let _permit = pgsql_permit(table_name, pgsql_limit);
+    let mut client = connect_pgsql(connection)?;
+    let deadline = Instant::now() + table_config.verify_timeout;
 
-    for (col_name, col_type) in &table_config.schema {
-        let sql_type = match col_type {
-            ColType::Symbol => "SYMBOL",
-            ColType::Timestamp => "TIMESTAMP",
-            ColType::Long => "LONG",
-            ColType::Double => "DOUBLE",
-        };
-        column_defs.push(format!("{} {}", col_name, sql_type));
+    loop {
+        let count = count_rows(&mut client, table_name)?;
+        if count == expected {
+            info!("Table '{}' has the expected {} rows", table_name, count);
+            return Ok(());
+        }
+        // More rows than sent won't go away by waiting
+        if count > expected || Instant::now() >= deadline {
+            warn!(
+                "Table '{}' has {} rows, expected {}. Rows may have been dropped, \
+                 deduplicated or not yet applied within verify_timeout",
+                table_name, count, expected
+            );
+            return Ok(());
+        }
+        debug!(
+            "Table '{}' has {} of {} rows, waiting for the server to apply the rest",
+            table_name, count, expected
+        );
+        thread::sleep(VERIFY_POLL_INTERVAL);
     }
+}
 
-    create_sql.push_str(&column_defs.join(", "));
-    create_sql.push_str(&format!(
-        ") TIMESTAMP({}) PARTITION BY DAY",
-        table_config.designated_ts
-    ));
+fn drop_and_create_table(
+    client: &mut Client,
+    table_name: &str,
+    table_config: &Table,
+) -> Result<()> {
+    info!("Dropping and recreating table '{}'", table_name);
 
-    debug!("Creating table with SQL: {}", create_sql);
-    client.execute(&create_sql, &[]).with_context(|| {
+    let ddl = table_ddl(table_name, table_config).unwrap_or_default();
+    debug!("Recreating table with SQL: {}", ddl);
+    client.batch_execute(&ddl).with_context(|| {
         format!(
-            "Failed to create table '{}' with SQL: {}",
-            table_name, create_sql
+            "Failed to drop and recreate table '{}' with SQL: {}",
+            table_name, ddl
         )
     })?;
 
@@ -424,15 +3646,649 @@ fn drop_and_create_table(
     Ok(())
 }
 
+/// DDL that `prepare_table` runs for the table's mode, `None` when it only checks the schema
+fn table_ddl(table_name: &str, table_config: &Table) -> Option<String> {
+    let create_sql = create_table_sql(table_name, table_config);
+    match table_config.table_mode {
+        // QuestDB auto-commits DDL even inside BEGIN/COMMIT, so a transaction would not make
+        // the drop and create atomic. Sending both in one simple-query round trip instead
+        // keeps the window where the table doesn't exist as short as possible.
+        TableMode::Recreate => Some(format!(
+            "DROP TABLE IF EXISTS {};\n{};",
+            quote_ident(table_name),
+            create_sql
+        )),
+        TableMode::Append => None,
+        TableMode::CreateIfMissing => Some(format!(
+            "{};",
+            create_sql.replacen("CREATE TABLE", "CREATE TABLE IF NOT EXISTS", 1)
+        )),
+    }
+}
+
+/// Fails unless the existing table has every configured column with the configured type.
+/// Extra columns are allowed, ILP leaves them null.
+fn check_existing_schema(
+    client: &mut Client,
+    table_name: &str,
+    table_config: &Table,
+) -> Result<()> {
+    let sql = format!(
+        "SELECT \"column\", \"type\" FROM table_columns('{}')",
+        table_name.replace('\'', "''")
+    );
+    let existing: HashMap<String, String> = client
+        .simple_query(&sql)
+        .with_context(|| format!("Failed to read the schema of table '{}'", table_name))?
+        .iter()
+        .filter_map(|message| match message {
+            SimpleQueryMessage::Row(row) => Some((
+                row.get(0).unwrap_or_default().to_string(),
+                row.get(1).unwrap_or_default().to_string(),
+            )),
+            _ => None,
+        })
+        .collect();
+    if existing.is_empty() {
+        return Err(anyhow::anyhow!("Table '{}' does not exist", table_name));
+    }
+    compare_schema(table_name, table_config, &existing)?;
+
+    info!(
+        "Existing table '{}' matches the configured schema",
+        table_name
+    );
+    Ok(())
+}
+
+/// Checks the configured columns against the existing table's, by name and SQL type. Names
+/// are compared lowercased, as QuestDB's are case-insensitive.
+fn compare_schema(
+    table_name: &str,
+    table_config: &Table,
+    existing: &HashMap<String, String>,
+) -> Result<()> {
+    let existing: HashMap<String, &String> = existing
+        .iter()
+        .map(|(name, sql)| (name.to_lowercase(), sql))
+        .collect();
+    for (col_name, col_type) in &table_config.schema {
+        let expected = sql_type(col_type);
+        match existing.get(&col_name.to_lowercase()) {
+            Some(actual) if actual.eq_ignore_ascii_case(&expected) => {}
+            Some(actual) => {
+                return Err(anyhow::anyhow!(
+                    "Column '{}' of table '{}' is {}, but the config expects {}",
+                    col_name,
+                    table_name,
+                    actual,
+                    expected
+                ));
+            }
+            None => {
+                return Err(anyhow::anyhow!(
+                    "Table '{}' has no column '{}'",
+                    table_name,
+                    col_name
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// QuestDB SQL type of a column type
+fn sql_type(col_type: &ColType) -> Cow<'static, str> {
+    let sql = match col_type {
+        ColType::Symbol => "SYMBOL",
+        ColType::Timestamp => "TIMESTAMP",
+        ColType::Date => "DATE",
+        ColType::Long => "LONG",
+        ColType::Int => "INT",
+        ColType::Short => "SHORT",
+        ColType::Byte => "BYTE",
+        ColType::Float => "FLOAT",
+        ColType::Double => "DOUBLE",
+        ColType::Varchar => "VARCHAR",
+        ColType::Char => "CHAR",
+        ColType::Ipv4 => "IPV4",
+        ColType::Long256 => "LONG256",
+        ColType::GeoHash { precision } => return precision.as_sql().into(),
+        ColType::DoubleArray => "DOUBLE[]",
+    };
+    sql.into()
+}
+
+/// Quotes a table or column name for SQL, so names such as reserved words or names with
+/// spaces work in DDL
+fn quote_ident(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+/// Builds the CREATE TABLE statement for the schema configuration
+fn create_table_sql(table_name: &str, table_config: &Table) -> String {
+    let mut create_sql = format!("CREATE TABLE {} (", quote_ident(table_name));
+    let mut column_defs = Vec::new();
+
+    for (col_name, col_type) in &table_config.schema {
+        column_defs.push(format!("{} {}", quote_ident(col_name), sql_type(col_type)));
+    }
+
+    create_sql.push_str(&column_defs.join(", "));
+    create_sql.push(')');
+    // Without a designated timestamp the table can't be partitioned
+    if let Some(designated_ts) = &table_config.designated_ts {
+        create_sql.push_str(&format!(
+            " TIMESTAMP({}) PARTITION BY {}",
+            quote_ident(designated_ts),
+            table_config.partition_by.as_sql()
+        ));
+    }
+    match table_config.wal {
+        Some(true) => create_sql.push_str(" WAL"),
+        Some(false) => create_sql.push_str(" BYPASS WAL"),
+        None => {}
+    }
+    if !table_config.dedup_keys.is_empty() {
+        // The designated timestamp is always part of the upsert keys
+        let keys: Vec<String> = table_config
+            .designated_ts
+            .iter()
+            .map(String::as_str)
+            .chain(
+                table_config
+                    .dedup_keys
+                    .iter()
+                    .map(String::as_str)
+                    .filter(|key| !table_config.is_designated_ts(key)),
+            )
+            .map(quote_ident)
+            .collect();
+        create_sql.push_str(&format!(" DEDUP UPSERT KEYS({})", keys.join(", ")));
+    }
+    create_sql
+}
+
 // Make SendSettings cloneable for the threads
 impl Clone for crate::settings::SendSettings {
     fn clone(&self) -> Self {
         Self {
             batch_pause: self.batch_pause,
+            pause_distribution: self.pause_distribution,
             batch_size: self.batch_size,
             parallel_senders: self.parallel_senders,
             tot_rows: self.tot_rows,
             batches_connection_keepalive: self.batches_connection_keepalive,
+            duration: self.duration,
+            ts_mode: self.ts_mode,
+            activity_profile: self.activity_profile.clone(),
+            row_interval: self.row_interval,
+            flush_bytes_threshold: self.flush_bytes_threshold,
+            ts_grid: self.ts_grid,
+            warmup_rows: self.warmup_rows,
+            ts_start: self.ts_start,
+            ts_end: self.ts_end,
+            ordered_timestamps: self.ordered_timestamps,
+            out_of_order_rate: self.out_of_order_rate,
+            out_of_order_lag: self.out_of_order_lag,
+            retry: self.retry.clone(),
+            max_reconnects: self.max_reconnects,
+            max_rows_per_sec: self.max_rows_per_sec,
+            ramp: self.ramp.clone(),
+            target_rate: self.target_rate,
+            progress_interval: self.progress_interval,
+            log_every: self.log_every,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    /// Table of the given schema, sending one row per batch from one sender
+    fn table(schema: serde_json::Value, designated_ts: Option<&str>) -> Table {
+        serde_json::from_value(json!({
+            "schema": schema,
+            "designated_ts": designated_ts,
+            "send": {
+                "batch_pause": ["0s", "0s"],
+                "batch_size": [1, 1],
+                "parallel_senders": 1,
+                "batches_connection_keepalive": 1,
+            },
+        }))
+        .unwrap()
+    }
+
+    fn data_gen(seed: u64) -> DataGenerator {
+        DataGenerator::new(Utc::now(), Vec::new(), StdRng::seed_from_u64(seed))
+    }
+
+    /// Designated timestamp of the last row in the buffer, in nanoseconds whatever unit it
+    /// was written in
+    fn designated_nanos(buffer: &Buffer) -> i64 {
+        line_nanos(std::str::from_utf8(buffer.as_bytes()).unwrap().trim_end())
+    }
+
+    /// Designated timestamp of an ILP line, in nanoseconds
+    fn line_nanos(line: &str) -> i64 {
+        let field = line.rsplit(' ').next().unwrap();
+        match field.strip_suffix('t') {
+            Some(micros) => micros.parse::<i64>().unwrap() * 1_000,
+            None => field.trim_end_matches('n').parse().unwrap(),
+        }
+    }
+
+    #[test]
+    fn micros_precision_truncates_to_whole_micros() {
+        for nanos in [1_234_567_891, 1_000, 999, 0, -1, -1_001] {
+            let Timestamp::Micros(micros) = ilp_timestamp(TsPrecision::Micros, nanos) else {
+                panic!("micros precision must produce a micros timestamp");
+            };
+            assert_eq!(micros.as_i64(), nanos.div_euclid(1_000));
+        }
+        let Timestamp::Nanos(nanos) = ilp_timestamp(TsPrecision::Nanos, 1_234_567_891) else {
+            panic!("nanos precision must produce a nanos timestamp");
+        };
+        assert_eq!(nanos.as_i64(), 1_234_567_891);
+    }
+
+    #[test]
+    fn micros_precision_designated_ts_is_whole_micros() {
+        for version in [ProtocolVersion::V1, ProtocolVersion::V2] {
+            let mut buffer = Buffer::new(version);
+            buffer
+                .table("t")
+                .unwrap()
+                .column_i64("x", 1)
+                .unwrap()
+                .at(ilp_timestamp(
+                    TsPrecision::Micros,
+                    1_700_000_000_123_456_789,
+                ))
+                .unwrap();
+            let ts = designated_nanos(&buffer);
+            assert_eq!(ts % 1_000, 0);
+            assert_eq!(ts, 1_700_000_000_123_456_000);
+        }
+    }
+
+    #[test]
+    fn designated_ts_must_be_a_timestamp_in_the_schema() {
+        let valid = table(json!([["ts", "Timestamp"], ["v", "Double"]]), Some("ts"));
+        assert!(validate_names("t", &valid).is_ok());
+
+        let missing = table(json!([["ts", "Timestamp"], ["v", "Double"]]), Some("tss"));
+        let error = validate_names("t", &missing).unwrap_err().to_string();
+        assert!(error.contains("not in the schema"), "{}", error);
+
+        let wrong_type = table(json!([["ts", "Long"], ["v", "Double"]]), Some("ts"));
+        let error = validate_names("t", &wrong_type).unwrap_err().to_string();
+        assert!(error.contains("must be a Timestamp"), "{}", error);
+    }
+
+    #[test]
+    fn float_and_double_columns_create_their_own_types() {
+        let mixed = table(
+            json!([["ts", "Timestamp"], ["f", "Float"], ["d", "Double"]]),
+            Some("ts"),
+        );
+        assert_eq!(
+            create_table_sql("t", &mixed),
+            "CREATE TABLE \"t\" (\"ts\" TIMESTAMP, \"f\" FLOAT, \"d\" DOUBLE) \
+             TIMESTAMP(\"ts\") PARTITION BY DAY"
+        );
+    }
+
+    #[test]
+    fn normal_distribution_samples_center_on_the_mean() {
+        const SAMPLES: usize = 100_000;
+        let sampler = ValueSampler::new(
+            &ValueDistribution::Normal {
+                mean: 50.0,
+                stddev: 10.0,
+            },
+            0.0,
+        )
+        .unwrap();
+        let mut data_gen = data_gen(7);
+
+        let doubles: f64 = (0..SAMPLES)
+            .map(|_| data_gen.generate_double(&sampler))
+            .sum();
+        assert!((doubles / SAMPLES as f64 - 50.0).abs() < 0.5);
+
+        let longs: i64 = (0..SAMPLES)
+            .map(|_| data_gen.generate_long((0, 100), &sampler))
+            .sum();
+        assert!((longs as f64 / SAMPLES as f64 - 50.0).abs() < 0.5);
+    }
+
+    #[test]
+    fn null_fraction_matches_the_null_rate() {
+        const ROWS: u32 = 20_000;
+        for null_rate in [0.05, 0.5, 1.0] {
+            let mut table = table(
+                json!([["ts", "Timestamp"], ["v", "Long"], ["w", "Long"]]),
+                Some("ts"),
+            );
+            table.seed = Some(11);
+            table.columns =
+                serde_json::from_value(json!({ "v": { "null_rate": null_rate } })).unwrap();
+            let source = RowSource::new("t", &table).unwrap();
+            let mut generator = RowGenerator::new(&source).unwrap();
+            let lines = std::str::from_utf8(generator.generate(ROWS).unwrap()).unwrap();
+            // "t v=1i,w=2i 1700000000000000000n", without `v=` when it was left null
+            let nulls = lines
+                .lines()
+                .filter(|line| {
+                    let fields = line.split(' ').nth(1).unwrap();
+                    !fields.split(',').any(|field| field.starts_with("v="))
+                })
+                .count();
+            let observed = nulls as f64 / f64::from(ROWS);
+            assert!(
+                (observed - null_rate).abs() < 0.02,
+                "null_rate {} gave {}",
+                null_rate,
+                observed
+            );
+        }
+    }
+
+    #[test]
+    fn equal_bounds_always_pick_the_bound() {
+        let send: SendSettings = serde_json::from_value(json!({
+            "batch_pause": ["1s", "1s"],
+            "batch_size": [500, 500],
+            "row_interval": ["1ms", "1ms"],
+            "parallel_senders": 1,
+            "batches_connection_keepalive": 1,
+            "tot_rows": 1000,
+        }))
+        .unwrap();
+        send.validate().unwrap();
+        let mut rng = StdRng::seed_from_u64(3);
+        let mut data_gen = data_gen(3);
+        for _ in 0..100 {
+            assert_eq!(random_pause(&send, &mut rng), Duration::from_secs(1));
+            assert_eq!(random_batch_size(send.batch_size, 1000, &mut rng), 500);
+            assert_eq!(data_gen.generate_long((7, 7), &ValueSampler::Uniform), 7);
+            assert_eq!(data_gen.generate_timestamp(Some((-5, -5)), 100), 95);
+        }
+
+        // Every row advances the designated timestamp by exactly the one interval
+        let mut table = table(json!([["ts", "Timestamp"], ["v", "Long"]]), Some("ts"));
+        table.seed = Some(3);
+        table.send.row_interval = (Duration::from_millis(1), Duration::from_millis(1));
+        let source = RowSource::new("t", &table).unwrap();
+        let mut generator = RowGenerator::new(&source).unwrap();
+        let lines = std::str::from_utf8(generator.generate(100).unwrap()).unwrap();
+        let timestamps: Vec<i64> = lines.lines().map(line_nanos).collect();
+        assert_eq!(timestamps.len(), 100);
+        for pair in timestamps.windows(2) {
+            assert_eq!(pair[1] - pair[0], 1_000_000);
+        }
+
+        let exponential = SendSettings {
+            pause_distribution: PauseDistribution::Exponential {
+                mean: Duration::from_millis(10),
+            },
+            ..send.clone()
+        };
+        assert_eq!(random_pause(&exponential, &mut rng), Duration::from_secs(1));
+    }
+
+    #[test]
+    fn huge_max_batch_sizes_never_truncate_and_are_rejected() {
+        let mut rng = StdRng::seed_from_u64(5);
+        for _ in 0..100 {
+            assert!(random_batch_size((1, u32::MAX), 10, &mut rng) <= 10);
+            assert_eq!(
+                random_batch_size((u32::MAX, u32::MAX), u64::MAX, &mut rng),
+                u32::MAX
+            );
         }
+
+        let mut huge = table(json!([["ts", "Timestamp"], ["v", "Double"]]), Some("ts"));
+        huge.send.batch_size = (1, u32::MAX);
+        let error = estimate::check_row_size("t", &huge, 16, 8)
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("lower the max batch_size"), "{}", error);
+
+        huge.send.batch_size = (1, 1_000);
+        assert!(estimate::check_row_size("t", &huge, 16, 8).is_ok());
+    }
+
+    #[test]
+    fn reserved_words_and_spaces_are_quoted_in_ddl() {
+        assert_eq!(quote_ident("order"), "\"order\"");
+        assert_eq!(quote_ident("my table"), "\"my table\"");
+        assert_eq!(quote_ident("say \"hi\""), "\"say \"\"hi\"\"\"");
+
+        let mut reserved = table(
+            json!([
+                ["timestamp", "Timestamp"],
+                ["select", "Long"],
+                ["my value", "Double"]
+            ]),
+            Some("timestamp"),
+        );
+        reserved.table_mode = TableMode::Recreate;
+        assert_eq!(
+            table_ddl("my table", &reserved).unwrap(),
+            "DROP TABLE IF EXISTS \"my table\";\n\
+             CREATE TABLE \"my table\" (\"timestamp\" TIMESTAMP, \"select\" LONG, \
+             \"my value\" DOUBLE) TIMESTAMP(\"timestamp\") PARTITION BY DAY;"
+        );
+    }
+
+    #[test]
+    fn sequences_count_per_symbol_value_not_pool_slot() {
+        let pool = SymbolPool::from_values(&ColumnValues::Uniform(vec![
+            "a".to_string(),
+            "b".to_string(),
+            "a".to_string(),
+        ]))
+        .unwrap();
+        let mut data_gen =
+            DataGenerator::new(Utc::now(), vec![Arc::new(pool)], StdRng::seed_from_u64(1));
+        assert_eq!(data_gen.next_sequence(0, 0, 0), 1);
+        assert_eq!(data_gen.next_sequence(0, 0, 2), 2);
+        assert_eq!(data_gen.next_sequence(0, 0, 1), 1);
+        assert_eq!(data_gen.next_sequence(0, 0, 0), 3);
+        // Another column keeps its own counters
+        assert_eq!(data_gen.next_sequence(1, 0, 2), 1);
+    }
+
+    #[test]
+    fn auto_flush_is_http_only_and_excludes_the_byte_threshold() {
+        let connection = |ilp: &str| -> Connection {
+            serde_json::from_value(json!({
+                "ilp": ilp,
+                "pgsql": "host=localhost",
+                "http": { "auto_flush_rows": 1000, "auto_flush_interval": "1s" },
+            }))
+            .unwrap()
+        };
+        let http = connection("http::addr=localhost:9000;");
+        let conf = ilp_conf_string(&http, &http.ilp[0], false).unwrap();
+        assert!(!conf.contains("auto_flush"), "rendered {}", conf);
+
+        let tcp = connection("tcp::addr=localhost:9009;");
+        let err = ilp_conf_string(&tcp, &tcp.ilp[0], false).unwrap_err();
+        assert!(
+            err.to_string().contains("only supported over HTTP"),
+            "{}",
+            err
+        );
+
+        let mut table = table(json!([["ts", "Timestamp"]]), Some("ts"));
+        validate_auto_flush(&table, &http.http).unwrap();
+        table.send.flush_bytes_threshold = Some(1024);
+        let err = validate_auto_flush(&table, &http.http).unwrap_err();
+        assert!(err.to_string().contains("flush_bytes_threshold"), "{}", err);
+    }
+
+    #[test]
+    fn alphabet_pools_are_distinct_and_bounded_by_the_alphabet() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let alphabet = ['a', 'b'];
+        let pool =
+            SymbolPool::new("s", Some(500), 20, None, Some(&alphabet), None, &mut rng).unwrap();
+        let distinct: HashSet<&String> = pool.values.iter().collect();
+        assert_eq!(distinct.len(), 500);
+
+        // Without a cardinality the pool is just capped at what the alphabet can make
+        let single = ['x'];
+        let pool = SymbolPool::new("s", None, 20, None, Some(&single), None, &mut rng).unwrap();
+        assert_eq!(pool.values.len(), alphabet_symbol_count(1));
+
+        let err = SymbolPool::new("s", Some(10), 20, None, Some(&single), None, &mut rng)
+            .err()
+            .unwrap();
+        assert!(err.to_string().contains("distinct values"), "{}", err);
+    }
+
+    #[test]
+    fn array_schemas_ask_for_protocol_version_2() {
+        let connection = |ilp: &str| -> Connection {
+            serde_json::from_value(json!({ "ilp": ilp, "pgsql": "host=localhost" })).unwrap()
+        };
+        let tcp = connection("tcp::addr=localhost:9009;");
+        let conf = ilp_conf_string(&tcp, &tcp.ilp[0], true).unwrap();
+        assert!(conf.ends_with("protocol_version=2;"), "rendered {}", conf);
+        let conf = ilp_conf_string(&tcp, &tcp.ilp[0], false).unwrap();
+        assert!(!conf.contains("protocol_version"), "rendered {}", conf);
+
+        let v1 = connection("tcp::addr=localhost:9009;protocol_version=1;");
+        let err = ilp_conf_string(&v1, &v1.ilp[0], true).unwrap_err();
+        assert!(err.to_string().contains("protocol_version=2"), "{}", err);
+        let auto = connection("http::addr=localhost:9000;protocol_version=auto;");
+        ilp_conf_string(&auto, &auto.ilp[0], true).unwrap();
+    }
+
+    #[test]
+    fn row_generator_writes_the_requested_rows() {
+        let mut table = table(json!([["ts", "Timestamp"], ["v", "Double"]]), Some("ts"));
+        table.seed = Some(5);
+        let source = RowSource::new("t", &table).unwrap();
+        let mut generator = RowGenerator::new(&source).unwrap();
+        // Doubles are binary in protocol version 2, so the rows are counted by the buffer
+        generator.generate(100).unwrap();
+        assert_eq!(generator.buffer.row_count(), 100);
+        // Each call replaces the previous rows
+        generator.generate(3).unwrap();
+        assert_eq!(generator.buffer.row_count(), 3);
+    }
+
+    #[test]
+    fn geohash_precisions_parse_without_overflowing() {
+        let precision = |value: &str| crate::col::GeoHashPrecision::try_from(value.to_string());
+        assert_eq!(precision("6c").unwrap().bits, 30);
+        assert_eq!(precision("30b").unwrap().as_sql(), "GEOHASH(6c)");
+        assert_eq!(precision("7b").unwrap().as_sql(), "GEOHASH(7b)");
+        assert!(precision("999999999c").is_err());
+        assert!(precision("61b").is_err());
+    }
+
+    #[test]
+    fn existing_columns_match_whatever_their_case() {
+        let table = table(
+            json!([["ts", "Timestamp"], ["Price", "Double"]]),
+            Some("ts"),
+        );
+        let existing: HashMap<String, String> = [("TS", "TIMESTAMP"), ("price", "DOUBLE")]
+            .into_iter()
+            .map(|(name, sql)| (name.to_string(), sql.to_string()))
+            .collect();
+        compare_schema("t", &table, &existing).unwrap();
+
+        let long: HashMap<String, String> = [("ts", "TIMESTAMP"), ("price", "LONG")]
+            .into_iter()
+            .map(|(name, sql)| (name.to_string(), sql.to_string()))
+            .collect();
+        let err = compare_schema("t", &table, &long).unwrap_err();
+        assert!(err.to_string().contains("expects DOUBLE"), "{}", err);
+    }
+
+    #[test]
+    fn ilp_addrs_split_into_host_and_port() {
+        assert_eq!(
+            split_host_port("localhost:9000", 1).unwrap(),
+            ("localhost", 9000)
+        );
+        assert_eq!(split_host_port("localhost", 1).unwrap(), ("localhost", 1));
+        assert_eq!(split_host_port("[::1]:9000", 1).unwrap(), ("::1", 9000));
+        assert_eq!(split_host_port("[::1]", 1).unwrap(), ("::1", 1));
+        assert_eq!(split_host_port("::1", 1).unwrap(), ("::1", 1));
+        assert!(split_host_port("[::1:9000", 1).is_err());
+        assert!(split_host_port("localhost:http", 1).is_err());
+    }
+
+    #[test]
+    fn drifting_short_columns_saturate_within_their_range() {
+        let mut table = table(json!([["ts", "Timestamp"], ["v", "Short"]]), Some("ts"));
+        table.seed = Some(13);
+        table.columns = serde_json::from_value(json!({ "v": { "drift": 1e12 } })).unwrap();
+        let source = RowSource::new("t", &table).unwrap();
+        let mut generator = RowGenerator::new(&source).unwrap();
+        let lines = std::str::from_utf8(generator.generate(50).unwrap()).unwrap();
+        let values: Vec<i64> = lines
+            .lines()
+            .map(|line| {
+                let field = line.split(' ').nth(1).unwrap();
+                field
+                    .strip_prefix("v=")
+                    .unwrap()
+                    .trim_end_matches('i')
+                    .parse()
+                    .unwrap()
+            })
+            .collect();
+        assert!(values.iter().all(|&value| value <= i64::from(i16::MAX)));
+        assert_eq!(values.last(), Some(&i64::from(i16::MAX)));
+    }
+
+    #[test]
+    fn blast_tables_reports_in_config_order_on_bounded_workers() {
+        let tables: IndexMap<String, Table> = (0..6)
+            .map(|i| {
+                let schema = json!([["ts", "Timestamp"], ["v", "Long"]]);
+                (format!("t{}", i), table(schema, Some("ts")))
+            })
+            .collect();
+        let connection = Connection {
+            ilp: vec!["http::addr=localhost:9000;".to_string()],
+            pgsql: String::new(),
+            pgsql_tls: false,
+            pgsql_tls_ca: None,
+            compression: None,
+            http: HttpSettings::default(),
+            username: None,
+            password: None,
+            token: None,
+        };
+        let options = BlastOptions {
+            latency_trace: None,
+            completion_webhook: None,
+            pre_flush_hook: Arc::new(NoopHook),
+            pgsql_limit: None,
+            sender_limit: None,
+            shutdown: Arc::new(AtomicBool::new(false)),
+            dry_run: true,
+            validate_only: false,
+            checkpoint: None,
+            resume: None,
+            metrics: None,
+            generate_only: false,
+        };
+        let reports = blast_tables(&tables, &connection, &options, 2).unwrap();
+        let names: Vec<_> = reports.iter().map(|report| report.table.as_str()).collect();
+        assert_eq!(names, ["t0", "t1", "t2", "t3", "t4", "t5"]);
+        assert!(reports.iter().all(TableReport::is_success));
     }
 }
@@ -0,0 +1,132 @@
+use std::{
+    collections::HashMap,
+    fs::{self, File},
+    io::{BufWriter, Write},
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+/// Where a sender stopped, enough to continue its rows and timestamps after a restart
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SenderProgress {
+    /// Rows flushed after warmup, including those of any earlier run it resumed
+    pub rows_sent: u64,
+    /// Designated timestamp of the last row sent in nanoseconds, the next row follows it
+    pub timestamp: i64,
+    /// Next value of each sequence column, by field column
+    pub counters: Vec<(usize, i64)>,
+    /// Last value of each per-symbol sequence, by field column and symbol value
+    pub sequences: Vec<(usize, String, i64)>,
+    /// Events started so far, so event ids stay unique
+    pub events_started: u64,
+}
+
+/// Progress of every sender of a table, indexed by sender id
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TableProgress {
+    pub senders: Vec<Option<SenderProgress>>,
+}
+
+/// Progress of a whole run, as written to and resumed from a checkpoint file
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Checkpoint {
+    pub tables: HashMap<String, TableProgress>,
+}
+
+impl Checkpoint {
+    pub fn load(path: &str) -> Result<Self> {
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read checkpoint '{}'", path))?;
+        let checkpoint: Self = serde_json::from_str(&contents)
+            .with_context(|| format!("Invalid checkpoint '{}'", path))?;
+        info!(
+            "Resuming {} table(s) from checkpoint '{}'",
+            checkpoint.tables.len(),
+            path
+        );
+        Ok(checkpoint)
+    }
+
+    pub fn table(&self, table_name: &str) -> Option<&TableProgress> {
+        self.tables.get(table_name)
+    }
+
+    /// Rows the table's senders already sent in the runs being resumed
+    pub fn rows_sent(&self, table_name: &str) -> u64 {
+        self.table(table_name).map_or(0, |table| {
+            table
+                .senders
+                .iter()
+                .flatten()
+                .map(|sender| sender.rows_sent)
+                .sum()
+        })
+    }
+}
+
+/// Collects every sender's progress and rewrites the checkpoint file at most once per
+/// `interval`, so a failed run can be resumed with `--resume`
+pub struct CheckpointWriter {
+    path: String,
+    interval: Duration,
+    state: Mutex<(Checkpoint, Instant)>,
+}
+
+impl CheckpointWriter {
+    /// `initial` carries the progress of a resumed run, kept for tables not run again
+    pub fn new(path: &str, interval: Duration, initial: Checkpoint) -> Self {
+        Self {
+            path: path.to_string(),
+            interval,
+            state: Mutex::new((initial, Instant::now())),
+        }
+    }
+
+    /// Records a sender's progress after a flush, writing the file if it is due
+    pub fn update(
+        &self,
+        table_name: &str,
+        parallel_senders: u16,
+        sender_id: u16,
+        progress: SenderProgress,
+    ) {
+        let mut state = self.state.lock().unwrap();
+        let (checkpoint, written_at) = &mut *state;
+        let table = checkpoint.tables.entry(table_name.to_string()).or_default();
+        table.senders.resize(parallel_senders.into(), None);
+        table.senders[usize::from(sender_id)] = Some(progress);
+
+        if written_at.elapsed() >= self.interval {
+            if let Err(e) = self.write_file(checkpoint) {
+                warn!("{:#}", e);
+            }
+            *written_at = Instant::now();
+        }
+    }
+
+    /// Writes the latest progress, e.g. once the run is over
+    pub fn finish(&self) -> Result<()> {
+        let state = self.state.lock().unwrap();
+        self.write_file(&state.0)?;
+        info!("Wrote checkpoint to '{}'", self.path);
+        Ok(())
+    }
+
+    /// Writes to a temporary file first, so a crash mid-write never corrupts the checkpoint
+    fn write_file(&self, checkpoint: &Checkpoint) -> Result<()> {
+        let tmp_path = format!("{}.tmp", self.path);
+        let file = File::create(&tmp_path)
+            .with_context(|| format!("Failed to create checkpoint '{}'", tmp_path))?;
+        let mut writer = BufWriter::new(file);
+        serde_json::to_writer(&mut writer, checkpoint)
+            .map_err(anyhow::Error::from)
+            .and_then(|()| writer.flush().map_err(anyhow::Error::from))
+            .with_context(|| format!("Failed to write checkpoint '{}'", tmp_path))?;
+        fs::rename(&tmp_path, &self.path)
+            .with_context(|| format!("Failed to replace checkpoint '{}'", self.path))
+    }
+}
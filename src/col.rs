@@ -1,11 +1,86 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 pub type ColName = String;
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone)]
 pub enum ColType {
     Symbol,
     Timestamp,
+    /// Milliseconds since the epoch, QuestDB's DATE
+    Date,
     Long,
+    Int,
+    Short,
+    Byte,
+    Float,
     Double,
+    Varchar,
+    Char,
+    Ipv4,
+    Long256,
+    /// e.g. `{ GeoHash = { precision = "6c" } }`
+    GeoHash {
+        precision: GeoHashPrecision,
+    },
+    DoubleArray,
+}
+
+/// Precision of a GeoHash column, e.g. "6c" for 6 characters or "30b" for 30 bits
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(try_from = "String", into = "String")]
+pub struct GeoHashPrecision {
+    pub bits: u32,
+}
+
+impl GeoHashPrecision {
+    /// Base32 characters needed to hold every bit
+    pub fn chars(self) -> usize {
+        self.bits.div_ceil(5) as usize
+    }
+
+    /// QuestDB's own rendering, in characters whenever the bits allow it
+    pub fn as_sql(self) -> String {
+        if self.bits.is_multiple_of(5) {
+            format!("GEOHASH({}c)", self.bits / 5)
+        } else {
+            format!("GEOHASH({}b)", self.bits)
+        }
+    }
+}
+
+impl From<GeoHashPrecision> for String {
+    fn from(precision: GeoHashPrecision) -> Self {
+        if precision.bits.is_multiple_of(5) {
+            format!("{}c", precision.bits / 5)
+        } else {
+            format!("{}b", precision.bits)
+        }
+    }
+}
+
+impl TryFrom<String> for GeoHashPrecision {
+    type Error = String;
+
+    fn try_from(precision: String) -> Result<Self, Self::Error> {
+        let invalid = || {
+            format!(
+                "Invalid GeoHash precision '{}', expected 1c to 12c or 1b to 60b",
+                precision
+            )
+        };
+        let trimmed = precision.trim();
+        let (digits, bits_per_unit) = match trimmed.strip_suffix('c') {
+            Some(chars) => (chars, 5),
+            None => (trimmed.strip_suffix('b').ok_or_else(invalid)?, 1),
+        };
+        let bits = digits
+            .parse::<u32>()
+            .ok()
+            .and_then(|units| units.checked_mul(bits_per_unit))
+            .ok_or_else(invalid)?;
+        if !(1..=60).contains(&bits) {
+            return Err(invalid());
+        }
+        Ok(Self { bits })
+    }
 }
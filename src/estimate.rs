@@ -0,0 +1,220 @@
+use anyhow::Result;
+use tracing::{info, warn};
+
+use crate::{
+    blasting::{DEFAULT_ARRAY_LEN, VARCHAR_LEN},
+    col::ColType,
+    settings::{Table, TsPrecision},
+};
+
+/// Default `max_buf_size` of the QuestDB client, beyond which a flush fails
+pub const CLIENT_MAX_BUF_SIZE: usize = 100 * 1024 * 1024;
+
+/// Rows larger than this risk exceeding the server's default ILP receive buffer
+const SERVER_ROW_WARN_BYTES: usize = 64 * 1024;
+
+/// Longest text form of an i64 or u64, used as a bound for numeric ILP values
+const MAX_INT_DIGITS: usize = 20;
+
+/// Longest text form of an f64 as written by the client
+const MAX_DOUBLE_CHARS: usize = 24;
+
+/// Typical text lengths of generated values, for volume estimates rather than limits
+const AVG_LONG_DIGITS: usize = 6;
+const AVG_INT_DIGITS: usize = 10;
+const AVG_SHORT_DIGITS: usize = 5;
+const AVG_BYTE_DIGITS: usize = 3;
+const AVG_DOUBLE_CHARS: usize = 18;
+
+/// Digits of a nanosecond or microsecond timestamp in the present era
+const EPOCH_NANOS_DIGITS: usize = 19;
+const EPOCH_MICROS_DIGITS: usize = 16;
+/// Digits of a millisecond date in the present era
+const EPOCH_MILLIS_DIGITS: usize = 13;
+
+/// Upper bound of the binary header preceding a one-dimensional array's elements
+const ARRAY_HEADER_BYTES: usize = 16;
+
+/// Upper bound, in bytes, of a single serialized ILP row.
+/// `max_symbol_bytes` is the longest value any symbol column can generate.
+pub fn max_row_bytes(table_name: &str, table_config: &Table, max_symbol_bytes: usize) -> usize {
+    let mut bytes = table_name.len();
+
+    for (col_name, col_type) in &table_config.schema {
+        if table_config.is_designated_ts(col_name) {
+            continue;
+        }
+        // Separator (',' or ' ') plus `name=`
+        bytes += col_name.len() + 2;
+        bytes += match col_type {
+            ColType::Symbol => max_symbol_bytes,
+            // Trailing 'i' / 't' type suffix
+            ColType::Long
+            | ColType::Int
+            | ColType::Short
+            | ColType::Byte
+            | ColType::Timestamp
+            | ColType::Date => MAX_INT_DIGITS + 1,
+            ColType::Float | ColType::Double => MAX_DOUBLE_CHARS,
+            // Surrounding quotes
+            ColType::Varchar => VARCHAR_LEN.1 + 2,
+            // One character, escaped if it is a quote or backslash
+            ColType::Char => 4,
+            // Quoted dotted quad
+            ColType::Ipv4 => 17,
+            // Quoted `0x` plus 64 hex digits
+            ColType::Long256 => 68,
+            ColType::GeoHash { precision } => precision.chars() + 2,
+            // Binary header (type, element type, dimensions, shape) plus 8 bytes per element
+            ColType::DoubleArray => {
+                let array_len = table_config
+                    .columns
+                    .get(col_name)
+                    .and_then(|settings| settings.array_len)
+                    .unwrap_or(DEFAULT_ARRAY_LEN);
+                ARRAY_HEADER_BYTES + array_len.1 * 8
+            }
+        };
+    }
+
+    // ` <designated timestamp>\n`, or just the newline when the server assigns it
+    match table_config.designated_ts {
+        Some(_) => bytes + MAX_INT_DIGITS + 2,
+        None => bytes + 1,
+    }
+}
+
+/// Rough average size, in bytes, of a serialized ILP row, for predicting the total volume.
+/// `avg_symbol_bytes` is the mean length of the values the symbol columns pick from.
+pub fn avg_row_bytes(table_name: &str, table_config: &Table, avg_symbol_bytes: usize) -> usize {
+    let mut bytes = table_name.len();
+
+    for (col_name, col_type) in &table_config.schema {
+        if table_config.is_designated_ts(col_name) {
+            continue;
+        }
+        bytes += col_name.len() + 2;
+        bytes += match col_type {
+            ColType::Symbol => avg_symbol_bytes,
+            ColType::Long => AVG_LONG_DIGITS + 1,
+            ColType::Int => AVG_INT_DIGITS + 1,
+            ColType::Short => AVG_SHORT_DIGITS + 1,
+            ColType::Byte => AVG_BYTE_DIGITS + 1,
+            ColType::Timestamp => EPOCH_MICROS_DIGITS + 1,
+            ColType::Date => EPOCH_MILLIS_DIGITS + 1,
+            ColType::Float | ColType::Double => AVG_DOUBLE_CHARS,
+            ColType::Varchar => (VARCHAR_LEN.0 + VARCHAR_LEN.1) / 2 + 2,
+            ColType::Char => 3,
+            ColType::Ipv4 => 15,
+            ColType::Long256 => 68,
+            ColType::GeoHash { precision } => precision.chars() + 2,
+            ColType::DoubleArray => {
+                let (min_len, max_len) = table_config
+                    .columns
+                    .get(col_name)
+                    .and_then(|settings| settings.array_len)
+                    .unwrap_or(DEFAULT_ARRAY_LEN);
+                ARRAY_HEADER_BYTES + (min_len + max_len) / 2 * 8
+            }
+        };
+    }
+
+    let ts_digits = match table_config.ts_precision {
+        TsPrecision::Micros => EPOCH_MICROS_DIGITS,
+        TsPrecision::Nanos => EPOCH_NANOS_DIGITS,
+    };
+    match table_config.designated_ts {
+        Some(_) => bytes + ts_digits + 2,
+        None => bytes + 1,
+    }
+}
+
+/// Estimated on-wire ILP volume of the table's whole run, logged for capacity planning
+pub fn estimate_total_bytes(
+    table_name: &str,
+    table_config: &Table,
+    avg_symbol_bytes: usize,
+) -> u64 {
+    let row_bytes = avg_row_bytes(table_name, table_config, avg_symbol_bytes) as u64;
+    let total = row_bytes.saturating_mul(table_config.send.tot_rows);
+    info!(
+        "Table '{}' will send ~{} of ILP ({} rows of ~{} bytes)",
+        table_name,
+        format_bytes(total),
+        table_config.send.tot_rows,
+        row_bytes
+    );
+    total
+}
+
+/// Formats a byte count with a binary unit, e.g. "1.5 GiB"
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KiB", "MiB", "GiB", "TiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
+
+/// Fails if a single row, or a typical batch of the largest size, would exceed the client
+/// buffer, and warns when rows or whole batches could plausibly hit server or client limits
+/// mid-run
+pub fn check_row_size(
+    table_name: &str,
+    table_config: &Table,
+    max_symbol_bytes: usize,
+    avg_symbol_bytes: usize,
+) -> Result<()> {
+    let row_bytes = max_row_bytes(table_name, table_config, max_symbol_bytes);
+    info!(
+        "Table '{}' rows are at most ~{} bytes each",
+        table_name, row_bytes
+    );
+
+    if row_bytes > CLIENT_MAX_BUF_SIZE {
+        return Err(anyhow::anyhow!(
+            "Table '{}' rows can reach ~{} bytes, above the client buffer limit of {} bytes",
+            table_name,
+            row_bytes,
+            CLIENT_MAX_BUF_SIZE
+        ));
+    }
+
+    if row_bytes > SERVER_ROW_WARN_BYTES {
+        warn!(
+            "Table '{}' rows can reach ~{} bytes, which may exceed the server's ILP receive buffer",
+            table_name, row_bytes
+        );
+    }
+
+    let max_batch = table_config.send.batch_size.1 as usize;
+    let avg_batch_bytes =
+        avg_row_bytes(table_name, table_config, avg_symbol_bytes).saturating_mul(max_batch);
+    if avg_batch_bytes > CLIENT_MAX_BUF_SIZE {
+        return Err(anyhow::anyhow!(
+            "Table '{}' batches of {} rows take ~{} bytes, above the client buffer limit of {} \
+             bytes, lower the max batch_size",
+            table_name,
+            max_batch,
+            avg_batch_bytes,
+            CLIENT_MAX_BUF_SIZE
+        ));
+    }
+
+    let batch_bytes = row_bytes.saturating_mul(max_batch);
+    if batch_bytes > CLIENT_MAX_BUF_SIZE {
+        warn!(
+            "Table '{}' batches of {} rows can reach ~{} bytes, above the client buffer limit of {} bytes",
+            table_name, max_batch, batch_bytes, CLIENT_MAX_BUF_SIZE
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,28 @@
+use anyhow::Result;
+use questdb::ingress::Buffer;
+
+/// Describes the batch a sender is about to flush
+#[derive(Debug)]
+pub struct FlushContext<'a> {
+    pub table_name: &'a str,
+    pub sender_id: u16,
+    /// Rows generated into the buffer for this batch
+    pub batch_size: u32,
+    /// Rows this sender flushed before this batch
+    pub rows_sent: u64,
+}
+
+/// Called by every sender right before each flush, e.g. to append sentinel rows or
+/// rewrite the buffer. Implement this to customize batches without forking the blaster.
+pub trait PreFlushHook: Send + Sync {
+    fn before_flush(&self, ctx: &FlushContext, buffer: &mut Buffer) -> Result<()>;
+}
+
+/// Leaves every batch untouched
+pub struct NoopHook;
+
+impl PreFlushHook for NoopHook {
+    fn before_flush(&self, _ctx: &FlushContext, _buffer: &mut Buffer) -> Result<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,79 @@
+use std::{sync::Mutex, time::Duration};
+
+use hdrhistogram::Histogram;
+use serde::Serialize;
+use tracing::info;
+
+/// Slowest flush a histogram tells apart from the others, in microseconds
+const MAX_LATENCY_MICROS: u64 = 3_600_000_000;
+
+/// Histogram of one sender's flush latencies in microseconds, at 3 significant digits
+pub fn new_histogram() -> Histogram<u64> {
+    Histogram::new_with_bounds(1, MAX_LATENCY_MICROS, 3).expect("valid histogram bounds")
+}
+
+pub fn record(histogram: &mut Histogram<u64>, latency: Duration) {
+    histogram.saturating_record(latency.as_micros().try_into().unwrap_or(u64::MAX));
+}
+
+/// Flush latency percentiles of a table, as logged and written to the JSON report
+#[derive(Debug, Clone, Serialize)]
+pub struct LatencySummary {
+    pub flushes: u64,
+    pub p50_ms: f64,
+    pub p90_ms: f64,
+    pub p99_ms: f64,
+    pub max_ms: f64,
+}
+
+/// Flush latencies merged from every sender of a table as each one finishes
+#[derive(Debug)]
+pub struct LatencyCollector {
+    histogram: Mutex<Histogram<u64>>,
+}
+
+impl LatencyCollector {
+    pub fn new() -> Self {
+        Self {
+            histogram: Mutex::new(new_histogram()),
+        }
+    }
+
+    pub fn merge(&self, sender_histogram: &Histogram<u64>) {
+        // Both share the same bounds, so adding can't fail
+        let _ = self.histogram.lock().unwrap().add(sender_histogram);
+    }
+
+    /// Logs and returns the percentiles, `None` if nothing was flushed after warmup
+    pub fn summarize(&self, table_name: &str) -> Option<LatencySummary> {
+        let histogram = self.histogram.lock().unwrap();
+        if histogram.is_empty() {
+            return None;
+        }
+        let millis = |micros: u64| micros as f64 / 1000.0;
+        let summary = LatencySummary {
+            flushes: histogram.len(),
+            p50_ms: millis(histogram.value_at_quantile(0.5)),
+            p90_ms: millis(histogram.value_at_quantile(0.9)),
+            p99_ms: millis(histogram.value_at_quantile(0.99)),
+            max_ms: millis(histogram.max()),
+        };
+        info!(
+            "Table '{}' flush latency over {} flushes: p50 {:.3}ms, p90 {:.3}ms, p99 {:.3}ms, \
+             max {:.3}ms",
+            table_name,
+            summary.flushes,
+            summary.p50_ms,
+            summary.p90_ms,
+            summary.p99_ms,
+            summary.max_ms
+        );
+        Some(summary)
+    }
+}
+
+impl Default for LatencyCollector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
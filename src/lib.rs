@@ -0,0 +1,20 @@
+//! Blast QuestDB with test data. The binary drives these modules from a TOML config,
+//! and they can be reused to embed blasts or customize batches via [`hook::PreFlushHook`].
+
+pub mod blasting;
+pub mod checkpoint;
+pub mod col;
+pub mod estimate;
+pub mod hook;
+pub mod latency;
+pub mod metrics;
+pub mod pool;
+pub mod ratelimit;
+pub mod report;
+pub mod semaphore;
+pub mod server;
+pub mod settings;
+pub mod stats;
+pub mod symbols;
+pub mod trace;
+pub mod webhook;
@@ -1,75 +1,318 @@
-mod blasting;
-mod col;
-mod settings;
 use anyhow::{Context, Result};
 use config::Config;
-use settings::Settings;
-use std::env;
-use tracing::{error, info};
+use qdb_blaster::{
+    blasting::{self, BlastOptions},
+    checkpoint::{Checkpoint, CheckpointWriter},
+    hook::NoopHook,
+    metrics::{self, Metrics},
+    pool,
+    report::{RunReport, TableReport},
+    semaphore::Semaphore,
+    server,
+    settings::{Settings, TableMode},
+    trace::LatencyTrace,
+    webhook::{self, RunSummary},
+};
+use std::{
+    env,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::{Duration, Instant},
+};
+use tracing::{Level, error, info, level_filters::LevelFilter, warn};
+use tracing_subscriber::EnvFilter;
 
 fn main() -> Result<()> {
-    // Initialize tracing
-    tracing_subscriber::fmt()
-        .with_max_level(tracing::Level::INFO)
-        .init();
-
-    let config_path = env::args()
-        .nth(1)
-        .context("Usage: myapp <config-file.toml>")?;
+    const USAGE: &str = "Usage: qdb-blaster [serve] [--dry-run] [--json-report <report.json>] \
+                         [--rows-override <rows>] [--tables <table,...>] \
+                         [--continue-on-error] [--validate-only] [--config-check] \
+                         [--checkpoint <checkpoint.json>] \
+                         [--resume <checkpoint.json>] <config-file.toml>";
+    const DEFAULT_CHECKPOINT_INTERVAL: Duration = Duration::from_secs(10);
+    let mut args = env::args().skip(1).peekable();
+    let serve_mode = args.next_if(|arg| arg == "serve").is_some();
+    let mut json_report = None;
+    let mut dry_run = false;
+    let mut validate_only = false;
+    let mut config_check = false;
+    let mut rows_override = None;
+    let mut table_filter = None;
+    let mut continue_on_error = false;
+    let mut checkpoint_path = None;
+    let mut resume_path = None;
+    while let Some(flag) = args.next_if(|arg| arg.starts_with("--")) {
+        match flag.as_str() {
+            "--json-report" => json_report = Some(args.next().context(USAGE)?),
+            "--dry-run" => dry_run = true,
+            "--validate-only" => validate_only = true,
+            "--config-check" => config_check = true,
+            "--rows-override" => {
+                let rows = args.next().context(USAGE)?;
+                rows_override = Some(
+                    rows.parse::<u64>()
+                        .with_context(|| format!("Invalid --rows-override '{}'", rows))?,
+                );
+            }
+            "--tables" => table_filter = Some(args.next().context(USAGE)?),
+            "--continue-on-error" => continue_on_error = true,
+            "--checkpoint" => checkpoint_path = Some(args.next().context(USAGE)?),
+            "--resume" => resume_path = Some(args.next().context(USAGE)?),
+            _ => return Err(anyhow::anyhow!("Unknown option '{}'\n{}", flag, USAGE)),
+        }
+    }
+    let config_path = args.next().context(USAGE)?;
+    if dry_run && validate_only {
+        return Err(anyhow::anyhow!(
+            "--dry-run and --validate-only cannot be combined\n{}",
+            USAGE
+        ));
+    }
 
-    let settings: Settings = Config::builder()
+    let mut settings: Settings = Config::builder()
         .add_source(config::File::with_name(&config_path))
         .build()
         .with_context(|| format!("Failed to load config from '{}'", config_path))?
         .try_deserialize()
         .context("Failed to deserialize config")?;
 
+    // Overrides only change the in-memory settings, never the config file
+    if let Some(filter) = &table_filter {
+        let wanted: Vec<&str> = filter.split(',').map(str::trim).collect();
+        if let Some(unknown) = wanted
+            .iter()
+            .find(|name| !settings.tables.contains_key(**name))
+        {
+            return Err(anyhow::anyhow!(
+                "Table '{}' passed to --tables is not in the config",
+                unknown
+            ));
+        }
+        settings
+            .tables
+            .retain(|name, _| wanted.contains(&name.as_str()));
+    }
+    if let Some(rows) = rows_override {
+        for table in settings.tables.values_mut() {
+            table.send.tot_rows = rows;
+            table.send.duration = None;
+        }
+    }
+    settings.continue_on_error |= continue_on_error;
+
+    // Initialize tracing, once the config says which level to log at
+    let level = match &settings.log_level {
+        Some(level) => level
+            .parse::<Level>()
+            .with_context(|| format!("Invalid log_level '{}'", level))?,
+        None if settings.debug => Level::DEBUG,
+        None => Level::INFO,
+    };
+    tracing_subscriber::fmt()
+        .with_env_filter(
+            EnvFilter::builder()
+                .with_default_directive(LevelFilter::from_level(level).into())
+                .from_env_lossy(),
+        )
+        .init();
+
     if settings.debug {
         eprintln!("Config:\n{:#?}", settings);
     }
 
-    info!("Starting QDB Blaster with {} tables", settings.tables.len());
+    settings.load_schema_files()?;
+    settings.validate()?;
+    // Printed before mixed tables move into their lead, so every table shows as configured
+    if config_check {
+        let resolved = serde_json::to_string_pretty(&settings)
+            .context("Failed to serialize the resolved config")?;
+        println!("{}", resolved);
+        return Ok(());
+    }
+    settings.resolve_mixed()?;
 
-    // Blast all tables in parallel
-    let mut handles = Vec::new();
-    for (table_name, table_config) in settings.tables {
-        let database_connection = settings.database.clone();
-        let table_name_for_thread = table_name.clone();
-        let handle = std::thread::spawn(move || {
-            if let Err(e) =
-                blasting::blast_table(&table_name_for_thread, &table_config, &database_connection)
-            {
-                tracing::error!("Table '{}' failed: {}", table_name_for_thread, e);
-                return Err(e);
+    // Resumed tables keep the rows already sent instead of being recreated
+    let resume = resume_path.as_deref().map(Checkpoint::load).transpose()?;
+    if let Some(checkpoint) = &resume {
+        for (table_name, table) in settings.tables.iter_mut() {
+            if checkpoint.table(table_name).is_some() && table.table_mode == TableMode::Recreate {
+                info!(
+                    "Appending to resumed table '{}' instead of recreating it",
+                    table_name
+                );
+                table.table_mode = TableMode::Append;
             }
-            tracing::info!("Table '{}' completed successfully", table_name_for_thread);
-            Ok(())
+        }
+    }
+    // A resumed run keeps checkpointing to the same file unless told otherwise
+    let checkpoint = checkpoint_path
+        .as_deref()
+        .or(resume_path.as_deref())
+        .filter(|_| !dry_run && !validate_only)
+        .map(|path| {
+            info!("Checkpointing progress to '{}'", path);
+            Arc::new(CheckpointWriter::new(
+                path,
+                settings
+                    .checkpoint_interval
+                    .unwrap_or(DEFAULT_CHECKPOINT_INTERVAL),
+                resume.clone().unwrap_or_default(),
+            ))
         });
-        handles.push((table_name, handle));
+
+    let latency_trace = match &settings.latency_trace {
+        Some(path) => {
+            info!("Recording per-flush latency trace to '{}'", path);
+            Some(Arc::new(LatencyTrace::create(path)?))
+        }
+        None => None,
+    };
+    let options = BlastOptions {
+        latency_trace,
+        completion_webhook: settings.completion_webhook.clone(),
+        pre_flush_hook: Arc::new(NoopHook),
+        pgsql_limit: settings
+            .max_pgsql_connections
+            .map(|max| Arc::new(Semaphore::new(max))),
+        sender_limit: settings
+            .max_total_senders
+            .map(|max| Arc::new(Semaphore::new(max))),
+        shutdown: Arc::new(AtomicBool::new(false)),
+        dry_run,
+        validate_only,
+        checkpoint,
+        resume: resume.map(Arc::new),
+        metrics: settings
+            .metrics_port
+            .filter(|_| !dry_run && !validate_only)
+            .map(|_| Arc::new(Metrics::new())),
+        generate_only: false,
+    };
+    let metrics_done = Arc::new(AtomicBool::new(false));
+    let metrics_server = match (settings.metrics_port, &options.metrics) {
+        (Some(port), Some(metrics)) => Some(metrics::serve(
+            port,
+            Arc::clone(metrics),
+            Arc::clone(&metrics_done),
+        )?),
+        _ => None,
+    };
+
+    if serve_mode {
+        return server::serve(&settings.server, settings.database, options);
     }
 
-    // Wait for all tables to complete
-    let mut errors = Vec::new();
-    for (table_name, handle) in handles {
-        match handle.join() {
-            Ok(Ok(())) => {
-                info!("Table '{}' processing completed", table_name);
-            }
-            Ok(Err(e)) => {
-                error!("Table '{}' failed: {}", table_name, e);
-                errors.push(format!("Table '{}': {}", table_name, e));
-            }
-            Err(_) => {
-                error!("Table '{}' thread panicked", table_name);
-                errors.push(format!("Table '{}': thread panicked", table_name));
-            }
+    // The first Ctrl-C lets senders finish their current batch, a second one exits at once
+    let shutdown = Arc::clone(&options.shutdown);
+    ctrlc::set_handler(move || {
+        if shutdown.swap(true, Ordering::Relaxed) {
+            std::process::exit(130);
+        }
+        warn!("Interrupted, stopping after the current batches (Ctrl-C again to abort)");
+    })
+    .context("Failed to install the Ctrl-C handler")?;
+
+    info!("Starting QDB Blaster with {} tables", settings.tables.len());
+    let run_start = Instant::now();
+    let table_count = settings.tables.len();
+
+    // Blast the tables on a bounded pool, one worker per core unless configured otherwise
+    let workers = settings
+        .max_concurrent_tables
+        .unwrap_or_else(pool::default_workers)
+        .clamp(1, table_count.max(1));
+    if workers < table_count {
+        info!(
+            "Blasting {} tables at a time, the others queue up in config order",
+            workers
+        );
+        if settings
+            .tables
+            .values()
+            .any(|table| table.send.is_unbounded())
+        {
+            warn!(
+                "Tables running until interrupted keep their worker, so tables queued behind \
+                 them may never start; raise max_concurrent_tables to run them all"
+            );
+        }
+    }
+    let finished = blasting::blast_tables(&settings.tables, &settings.database, &options, workers)?;
+
+    let mut reports = Vec::new();
+    for report in finished {
+        let table_name = &report.table;
+        match &report.error {
+            Some(e) => error!("Table '{}' failed: {}", table_name, e),
+            None if dry_run || validate_only => info!("Table '{}' validated", table_name),
+            None if report.rows_sent < report.rows_requested => warn!(
+                "Table '{}' interrupted after {} of {} rows",
+                table_name, report.rows_sent, report.rows_requested
+            ),
+            None => info!(
+                "Table '{}' completed, {} rows at {:.0} rows/sec",
+                table_name, report.rows_sent, report.rows_per_sec
+            ),
         }
+        reports.push(report);
+    }
+
+    if let Some(trace) = &options.latency_trace {
+        trace.finish()?;
+    }
+    if let Some(checkpoint) = &options.checkpoint {
+        checkpoint.finish()?;
+    }
+    if let Some(server) = metrics_server {
+        metrics_done.store(true, Ordering::Relaxed);
+        let _ = server.join();
     }
 
-    if !errors.is_empty() {
-        return Err(anyhow::anyhow!("Some tables failed: {}", errors.join(", ")));
+    let run_report = RunReport::new(reports, run_start.elapsed());
+    if let Some(path) = &json_report {
+        run_report.write(path)?;
     }
 
-    info!("All tables processed successfully");
+    if let Some(url) = &settings.completion_webhook
+        && !dry_run
+        && !validate_only
+    {
+        webhook::notify(
+            url,
+            &RunSummary {
+                success: run_report.success,
+                tables: table_count,
+                failed_tables: run_report.errors.len(),
+                elapsed_secs: run_report.elapsed_secs,
+                errors: run_report.errors.clone(),
+            },
+        );
+    }
+
+    // Failed tables stay in the report either way, only the exit status differs
+    let any_succeeded = run_report.tables.iter().any(TableReport::is_success);
+    if !run_report.success && (!settings.continue_on_error || !any_succeeded) {
+        return Err(anyhow::anyhow!(
+            "Some tables failed: {}",
+            run_report.errors.join(", ")
+        ));
+    }
+
+    if !run_report.success {
+        warn!(
+            "{} of {} tables failed, continuing on error",
+            run_report.errors.len(),
+            table_count
+        );
+    } else if dry_run {
+        info!("Dry run complete, nothing was sent");
+    } else if validate_only {
+        info!("Validation complete, pgwire and ILP are reachable and nothing was written");
+    } else if options.shutdown.load(Ordering::Relaxed) {
+        warn!("Run interrupted before all rows were sent");
+    } else {
+        info!("All tables processed successfully");
+    }
     Ok(())
 }
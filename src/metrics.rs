@@ -0,0 +1,232 @@
+use std::{
+    fmt::Write as _,
+    io::{BufRead, BufReader, ErrorKind, Write},
+    net::{Ipv4Addr, TcpListener, TcpStream},
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, AtomicU64, Ordering},
+    },
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+use anyhow::{Context, Result};
+use tracing::{info, warn};
+
+/// Upper bounds of the flush latency histogram buckets, in seconds
+const LATENCY_BUCKETS: [f64; 14] = [
+    0.0005, 0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+/// How often the metrics server checks whether the run is over between requests
+const ACCEPT_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Live counters of one table, scraped from `/metrics` while the run goes on
+#[derive(Debug)]
+pub struct TableMetrics {
+    /// The table's sent counter, rows flushed after warmup
+    rows_sent: Arc<AtomicU64>,
+    flush_errors: AtomicU64,
+    active_senders: AtomicU64,
+    /// Flushes per latency bucket, the last one counting those slower than every bound
+    latency_buckets: [AtomicU64; LATENCY_BUCKETS.len() + 1],
+    latency_sum_micros: AtomicU64,
+}
+
+impl TableMetrics {
+    /// Counts a failed flush attempt, whether or not it is retried
+    pub fn flush_failed(&self) {
+        self.flush_errors.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn observe_flush(&self, latency: Duration) {
+        let secs = latency.as_secs_f64();
+        let bucket = LATENCY_BUCKETS
+            .iter()
+            .position(|bound| secs <= *bound)
+            .unwrap_or(LATENCY_BUCKETS.len());
+        self.latency_buckets[bucket].fetch_add(1, Ordering::Relaxed);
+        self.latency_sum_micros.fetch_add(
+            latency.as_micros().try_into().unwrap_or(u64::MAX),
+            Ordering::Relaxed,
+        );
+    }
+
+    /// Counts a sender as active until the returned guard is dropped
+    pub fn sender_active(self: &Arc<Self>) -> ActiveSender {
+        self.active_senders.fetch_add(1, Ordering::Relaxed);
+        ActiveSender(Arc::clone(self))
+    }
+}
+
+/// Counts a sender as active for as long as it's alive, even if its thread fails
+pub struct ActiveSender(Arc<TableMetrics>);
+
+impl Drop for ActiveSender {
+    fn drop(&mut self) {
+        self.0.active_senders.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// Metrics of every table blasted so far, in the Prometheus text format
+#[derive(Debug, Default)]
+pub struct Metrics {
+    tables: Mutex<Vec<(String, Arc<TableMetrics>)>>,
+}
+
+impl Metrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Starts tracking a table, its rows read from the table's own sent counter
+    pub fn register(&self, table_name: &str, rows_sent: &Arc<AtomicU64>) -> Arc<TableMetrics> {
+        let table = Arc::new(TableMetrics {
+            rows_sent: Arc::clone(rows_sent),
+            flush_errors: AtomicU64::new(0),
+            active_senders: AtomicU64::new(0),
+            latency_buckets: Default::default(),
+            latency_sum_micros: AtomicU64::new(0),
+        });
+        let mut tables = self.tables.lock().unwrap();
+        // A table blasted again, e.g. by the server, starts from zero
+        tables.retain(|(name, _)| name != table_name);
+        tables.push((table_name.to_string(), Arc::clone(&table)));
+        table
+    }
+
+    /// Renders every table's metrics in the Prometheus text exposition format
+    pub fn render(&self) -> String {
+        let tables = self.tables.lock().unwrap();
+        let mut out = String::new();
+        let mut counter = |name: &str, kind: &str, help: &str, value: fn(&TableMetrics) -> u64| {
+            let _ = writeln!(out, "# HELP {} {}\n# TYPE {} {}", name, help, name, kind);
+            for (table_name, table) in tables.iter() {
+                let _ = writeln!(
+                    out,
+                    "{}{{table=\"{}\"}} {}",
+                    name,
+                    escape_label(table_name),
+                    value(table)
+                );
+            }
+        };
+        counter(
+            "qdb_blaster_rows_sent_total",
+            "counter",
+            "Rows flushed after warmup",
+            |table| table.rows_sent.load(Ordering::Relaxed),
+        );
+        counter(
+            "qdb_blaster_flush_errors_total",
+            "counter",
+            "Failed flush attempts, including retried ones",
+            |table| table.flush_errors.load(Ordering::Relaxed),
+        );
+        counter(
+            "qdb_blaster_active_senders",
+            "gauge",
+            "Sender threads currently running",
+            |table| table.active_senders.load(Ordering::Relaxed),
+        );
+
+        let name = "qdb_blaster_flush_latency_seconds";
+        let _ = writeln!(
+            out,
+            "# HELP {} Latency of flushes after warmup\n# TYPE {} histogram",
+            name, name
+        );
+        for (table_name, table) in tables.iter() {
+            let label = escape_label(table_name);
+            let mut cumulative = 0;
+            for (bucket, count) in table.latency_buckets.iter().enumerate() {
+                cumulative += count.load(Ordering::Relaxed);
+                let bound = LATENCY_BUCKETS
+                    .get(bucket)
+                    .map_or_else(|| "+Inf".to_string(), f64::to_string);
+                let _ = writeln!(
+                    out,
+                    "{}_bucket{{table=\"{}\",le=\"{}\"}} {}",
+                    name, label, bound, cumulative
+                );
+            }
+            let sum = table.latency_sum_micros.load(Ordering::Relaxed) as f64 / 1_000_000.0;
+            let _ = writeln!(out, "{}_sum{{table=\"{}\"}} {}", name, label, sum);
+            let _ = writeln!(out, "{}_count{{table=\"{}\"}} {}", name, label, cumulative);
+        }
+        out
+    }
+}
+
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// Serves `GET /metrics` on all interfaces at `port` until `done` is set
+pub fn serve(port: u16, metrics: Arc<Metrics>, done: Arc<AtomicBool>) -> Result<JoinHandle<()>> {
+    let listener = TcpListener::bind((Ipv4Addr::UNSPECIFIED, port))
+        .with_context(|| format!("Failed to bind metrics server to port {}", port))?;
+    // Non-blocking, so the server notices the end of the run without waiting for a scrape
+    listener
+        .set_nonblocking(true)
+        .context("Failed to configure the metrics server")?;
+    info!("Serving Prometheus metrics on port {} at /metrics", port);
+
+    Ok(thread::spawn(move || {
+        while !done.load(Ordering::Relaxed) {
+            match listener.accept() {
+                Ok((stream, _)) => {
+                    if let Err(e) = handle_scrape(stream, &metrics) {
+                        warn!("Failed to serve metrics: {}", e);
+                    }
+                }
+                Err(e) if e.kind() == ErrorKind::WouldBlock => thread::sleep(ACCEPT_POLL_INTERVAL),
+                Err(e) => warn!("Failed to accept metrics connection: {}", e),
+            }
+        }
+    }))
+}
+
+fn handle_scrape(mut stream: TcpStream, metrics: &Metrics) -> Result<()> {
+    // Accepted sockets inherit the listener's non-blocking mode
+    stream.set_nonblocking(false)?;
+    stream.set_read_timeout(Some(Duration::from_secs(5)))?;
+    let mut reader = BufReader::new(stream.try_clone()?);
+
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default().to_string();
+    let path = parts.next().unwrap_or_default().to_string();
+
+    // Scrapes carry no body, so the headers are just skipped
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 || header.trim().is_empty() {
+            break;
+        }
+    }
+
+    let (status, content_type, body) = match (method.as_str(), path.as_str()) {
+        ("GET", "/metrics") => ("200 OK", "text/plain; version=0.0.4", metrics.render()),
+        _ => (
+            "404 Not Found",
+            "text/plain",
+            format!("No route for {} {}\n", method, path),
+        ),
+    };
+
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        content_type,
+        body.len(),
+        body
+    )?;
+    stream.flush()?;
+    Ok(())
+}
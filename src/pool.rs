@@ -0,0 +1,102 @@
+use std::{num::NonZeroUsize, sync::Mutex, thread};
+
+use anyhow::{Context, Result};
+
+/// Workers used when no cap is configured, one per available core
+pub fn default_workers() -> usize {
+    thread::available_parallelism().map_or(1, NonZeroUsize::get)
+}
+
+/// Runs `job` on every item on at most `workers` threads named `{name}-{n}`, each taking the
+/// next item once its previous one completes, and returns the results in item order.
+/// A panicking job takes down the whole pool, so jobs catch their own panics.
+pub fn run<T, R>(
+    name: &str,
+    items: &[T],
+    workers: usize,
+    job: impl Fn(&T) -> R + Sync,
+) -> Result<Vec<R>>
+where
+    T: Sync,
+    R: Send,
+{
+    let workers = workers.clamp(1, items.len().max(1));
+    let queue = Mutex::new(items.iter().enumerate());
+    let finished = Mutex::new(Vec::with_capacity(items.len()));
+    thread::scope(|scope| -> Result<()> {
+        for worker in 0..workers {
+            thread::Builder::new()
+                .name(format!("{}-{}", name, worker))
+                .spawn_scoped(scope, || {
+                    loop {
+                        // Released before the job runs, so the workers run in parallel
+                        let Some((idx, item)) = queue.lock().unwrap().next() else {
+                            break;
+                        };
+                        let result = job(item);
+                        finished.lock().unwrap().push((idx, result));
+                    }
+                })
+                .with_context(|| format!("Failed to spawn {}-{}", name, worker))?;
+        }
+        Ok(())
+    })?;
+
+    let mut finished = finished.into_inner().unwrap();
+    finished.sort_by_key(|(idx, _)| *idx);
+    Ok(finished.into_iter().map(|(_, result)| result).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{
+        collections::HashSet,
+        sync::atomic::{AtomicUsize, Ordering},
+        time::Duration,
+    };
+
+    #[test]
+    fn jobs_run_on_at_most_the_given_workers() {
+        let running = AtomicUsize::new(0);
+        let peak = AtomicUsize::new(0);
+        let threads = Mutex::new(HashSet::new());
+        let items: Vec<usize> = (0..32).collect();
+        let results = run("table-worker", &items, 3, |item| {
+            let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+            peak.fetch_max(now, Ordering::SeqCst);
+            threads
+                .lock()
+                .unwrap()
+                .insert(thread::current().name().unwrap().to_string());
+            thread::sleep(Duration::from_millis(2));
+            running.fetch_sub(1, Ordering::SeqCst);
+            item * 2
+        })
+        .unwrap();
+
+        assert_eq!(
+            results,
+            items.iter().map(|item| item * 2).collect::<Vec<_>>()
+        );
+        assert!(peak.load(Ordering::SeqCst) <= 3);
+        let threads = threads.into_inner().unwrap();
+        assert!(!threads.is_empty() && threads.len() <= 3);
+        assert!(threads.iter().all(|name| name.starts_with("table-worker-")));
+    }
+
+    #[test]
+    fn workers_are_capped_by_the_items() {
+        let threads = Mutex::new(HashSet::new());
+        run("table-worker", &[1, 2], 8, |_| {
+            threads.lock().unwrap().insert(thread::current().id());
+        })
+        .unwrap();
+        assert!(threads.into_inner().unwrap().len() <= 2);
+        assert!(
+            run("table-worker", &[] as &[u8], 8, |_| ())
+                .unwrap()
+                .is_empty()
+        );
+    }
+}
@@ -0,0 +1,92 @@
+use std::{
+    sync::{Mutex, OnceLock},
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::settings::{RampSettings, RampShape};
+
+/// Token bucket capping the combined row rate of every sender sharing it.
+/// Callers reserve their rows up front and wait out any deficit, so waiters are served
+/// in arrival order and no sender can starve the others.
+#[derive(Debug)]
+pub struct RateLimiter {
+    rows_per_sec: f64,
+    /// Rate reached at the end of the ramp, the rate rising from `rows_per_sec` until then
+    ramp: Option<(f64, Duration, RampShape)>,
+    started_at: Instant,
+    /// Rate when a flush first failed
+    first_failure: OnceLock<f64>,
+    state: Mutex<BucketState>,
+}
+
+#[derive(Debug)]
+struct BucketState {
+    /// Rows that may be sent immediately, negative when already reserved ahead
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+impl RateLimiter {
+    pub fn new(rows_per_sec: u64) -> Self {
+        let now = Instant::now();
+        Self {
+            rows_per_sec: rows_per_sec as f64,
+            ramp: None,
+            started_at: now,
+            first_failure: OnceLock::new(),
+            state: Mutex::new(BucketState {
+                tokens: 0.0,
+                refilled_at: now,
+            }),
+        }
+    }
+
+    /// Rate rising from the ramp's start to its end rate, measured from now
+    pub fn ramped(ramp: &RampSettings) -> Self {
+        let mut limiter = Self::new(ramp.start_rows_per_sec);
+        limiter.ramp = Some((ramp.end_rows_per_sec as f64, ramp.ramp_duration, ramp.shape));
+        limiter
+    }
+
+    /// Current rows/sec ceiling
+    pub fn rate(&self) -> f64 {
+        self.rate_at(Instant::now())
+    }
+
+    fn rate_at(&self, now: Instant) -> f64 {
+        let Some((end, duration, shape)) = self.ramp else {
+            return self.rows_per_sec;
+        };
+        let progress = ((now - self.started_at).as_secs_f64() / duration.as_secs_f64()).min(1.0);
+        match shape {
+            RampShape::Exponential => self.rows_per_sec * (end / self.rows_per_sec).powf(progress),
+            RampShape::Linear => self.rows_per_sec + (end - self.rows_per_sec) * progress,
+        }
+    }
+
+    /// Records the current rate as the rate of the first failed flush, returning it only
+    /// to the first caller
+    pub fn first_failure(&self) -> Option<f64> {
+        let rate = self.rate();
+        self.first_failure.set(rate).ok().map(|()| rate)
+    }
+
+    /// Blocks until `rows` may be sent without exceeding the rate
+    pub fn acquire(&self, rows: u32) {
+        let wait = {
+            let mut state = self.state.lock().unwrap();
+            let now = Instant::now();
+            let rows_per_sec = self.rate_at(now);
+            // Allow at most one second of burst after an idle period
+            state.tokens = (state.tokens + (now - state.refilled_at).as_secs_f64() * rows_per_sec)
+                .min(rows_per_sec);
+            state.refilled_at = now;
+            state.tokens -= f64::from(rows);
+            (-state.tokens).max(0.0) / rows_per_sec
+        };
+        if wait > 0.0 {
+            thread::sleep(Duration::from_secs_f64(wait));
+        }
+    }
+}
@@ -0,0 +1,104 @@
+use std::{fs::File, io::BufWriter, time::Duration};
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use tracing::info;
+
+use crate::{latency::LatencySummary, stats::ColumnStats};
+
+/// Outcome and achieved throughput of blasting a single table
+#[derive(Debug, Clone, Serialize)]
+pub struct TableReport {
+    pub table: String,
+    pub rows_requested: u64,
+    pub rows_sent: u64,
+    /// Rough on-wire ILP volume of all requested rows, unknown if validation failed
+    pub estimated_bytes: Option<u64>,
+    pub elapsed_secs: f64,
+    pub rows_per_sec: f64,
+    pub error: Option<String>,
+    /// Generated values of each non-symbol column when the table sets `column_stats`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub column_stats: Option<Vec<ColumnStats>>,
+    /// Flush latency percentiles after warmup, unless nothing was flushed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub flush_latency: Option<LatencySummary>,
+    /// The table's `target_rate`, to compare `rows_per_sec` against
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target_rows_per_sec: Option<u64>,
+}
+
+impl TableReport {
+    pub fn new(
+        table: &str,
+        rows_requested: u64,
+        rows_sent: u64,
+        estimated_bytes: Option<u64>,
+        elapsed: Duration,
+        error: Option<String>,
+    ) -> Self {
+        Self {
+            table: table.to_string(),
+            rows_requested,
+            rows_sent,
+            estimated_bytes,
+            elapsed_secs: elapsed.as_secs_f64(),
+            rows_per_sec: rows_per_sec(rows_sent, elapsed),
+            error,
+            column_stats: None,
+            flush_latency: None,
+            target_rows_per_sec: None,
+        }
+    }
+
+    pub fn is_success(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// Machine-readable outcome of a whole run, e.g. for CI to assert on throughput
+#[derive(Debug, Serialize)]
+pub struct RunReport {
+    pub success: bool,
+    pub elapsed_secs: f64,
+    pub rows_sent: u64,
+    pub rows_per_sec: f64,
+    pub tables: Vec<TableReport>,
+    pub errors: Vec<String>,
+}
+
+impl RunReport {
+    pub fn new(tables: Vec<TableReport>, elapsed: Duration) -> Self {
+        let rows_sent = tables.iter().map(|table| table.rows_sent).sum();
+        let errors: Vec<String> = tables
+            .iter()
+            .filter_map(|table| {
+                let error = table.error.as_ref()?;
+                Some(format!("Table '{}': {}", table.table, error))
+            })
+            .collect();
+        Self {
+            success: errors.is_empty(),
+            elapsed_secs: elapsed.as_secs_f64(),
+            rows_sent,
+            rows_per_sec: rows_per_sec(rows_sent, elapsed),
+            tables,
+            errors,
+        }
+    }
+
+    /// Writes the report as pretty-printed JSON
+    pub fn write(&self, path: &str) -> Result<()> {
+        let file = File::create(path)
+            .with_context(|| format!("Failed to create JSON report '{}'", path))?;
+        serde_json::to_writer_pretty(BufWriter::new(file), self)
+            .with_context(|| format!("Failed to write JSON report '{}'", path))?;
+        info!("Wrote JSON report to '{}'", path);
+        Ok(())
+    }
+}
+
+fn rows_per_sec(rows: u64, elapsed: Duration) -> f64 {
+    let secs = elapsed.as_secs_f64();
+    if secs > 0.0 { rows as f64 / secs } else { 0.0 }
+}
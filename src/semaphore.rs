@@ -0,0 +1,90 @@
+use std::sync::{Arc, Condvar, Mutex};
+
+/// Counting semaphore bounding how many threads may hold a shared resource at once
+#[derive(Debug)]
+pub struct Semaphore {
+    permits: Mutex<usize>,
+    released: Condvar,
+}
+
+/// Returns its permit to the semaphore when dropped
+#[derive(Debug)]
+pub struct SemaphorePermit<'a> {
+    semaphore: &'a Semaphore,
+}
+
+/// A permit that can move to another thread, returned when dropped
+#[derive(Debug)]
+pub struct OwnedSemaphorePermit {
+    semaphore: Arc<Semaphore>,
+}
+
+impl Semaphore {
+    pub fn new(permits: usize) -> Self {
+        Self {
+            permits: Mutex::new(permits),
+            released: Condvar::new(),
+        }
+    }
+
+    /// Takes a permit if one is free, without blocking
+    pub fn try_acquire(&self) -> Option<SemaphorePermit<'_>> {
+        self.try_take()
+            .then_some(SemaphorePermit { semaphore: self })
+    }
+
+    /// Blocks until a permit is free
+    pub fn acquire(&self) -> SemaphorePermit<'_> {
+        self.take();
+        SemaphorePermit { semaphore: self }
+    }
+
+    /// Takes a permit if one is free, without blocking, for a thread that outlives the caller
+    pub fn try_acquire_owned(self: &Arc<Self>) -> Option<OwnedSemaphorePermit> {
+        self.try_take().then(|| OwnedSemaphorePermit {
+            semaphore: Arc::clone(self),
+        })
+    }
+
+    /// Blocks until a permit is free, for a thread that outlives the caller
+    pub fn acquire_owned(self: &Arc<Self>) -> OwnedSemaphorePermit {
+        self.take();
+        OwnedSemaphorePermit {
+            semaphore: Arc::clone(self),
+        }
+    }
+
+    fn try_take(&self) -> bool {
+        let mut permits = self.permits.lock().unwrap();
+        if *permits == 0 {
+            return false;
+        }
+        *permits -= 1;
+        true
+    }
+
+    fn take(&self) {
+        let mut permits = self
+            .released
+            .wait_while(self.permits.lock().unwrap(), |permits| *permits == 0)
+            .unwrap();
+        *permits -= 1;
+    }
+
+    fn release(&self) {
+        *self.permits.lock().unwrap() += 1;
+        self.released.notify_one();
+    }
+}
+
+impl Drop for SemaphorePermit<'_> {
+    fn drop(&mut self) {
+        self.semaphore.release();
+    }
+}
+
+impl Drop for OwnedSemaphorePermit {
+    fn drop(&mut self) {
+        self.semaphore.release();
+    }
+}
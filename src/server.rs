@@ -0,0 +1,211 @@
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    net::{TcpListener, TcpStream},
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+    thread,
+    time::Duration,
+};
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use tracing::{error, info, warn};
+
+use crate::{
+    blasting::{self, BlastOptions},
+    settings::{Connection, ServerSettings, Table},
+    webhook::TableSummary,
+};
+
+/// Largest request line and headers accepted, together
+const MAX_HEAD_BYTES: u64 = 16 * 1024;
+
+/// Largest request body accepted, far above any table config
+const MAX_BODY_BYTES: usize = 1024 * 1024;
+
+/// How long a client may take to send its request, so a stalled one can't hold a thread
+const REQUEST_READ_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Body of a `POST /blast` request
+#[derive(Debug, Deserialize)]
+struct BlastRequest {
+    table: String,
+    config: Table,
+}
+
+/// Shared state for all request handler threads
+struct ServerState {
+    connection: Connection,
+    options: BlastOptions,
+    max_concurrent_blasts: usize,
+    active_blasts: AtomicUsize,
+}
+
+/// Releases a blast slot when the request finishes, even on error
+struct BlastSlot<'a>(&'a AtomicUsize);
+
+impl Drop for BlastSlot<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Runs an HTTP server accepting `POST /blast` requests until the process is killed
+pub fn serve(server: &ServerSettings, connection: Connection, options: BlastOptions) -> Result<()> {
+    let listener = TcpListener::bind(&server.addr)
+        .with_context(|| format!("Failed to bind server to '{}'", server.addr))?;
+    info!(
+        "Serving blast API on '{}' (max {} concurrent blasts)",
+        server.addr, server.max_concurrent_blasts
+    );
+
+    let state = Arc::new(ServerState {
+        connection,
+        options,
+        max_concurrent_blasts: server.max_concurrent_blasts,
+        active_blasts: AtomicUsize::new(0),
+    });
+
+    for stream in listener.incoming() {
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(e) => {
+                warn!("Failed to accept connection: {}", e);
+                continue;
+            }
+        };
+        let state = Arc::clone(&state);
+        thread::spawn(move || {
+            if let Err(e) = handle_connection(stream, &state) {
+                warn!("Failed to handle request: {}", e);
+            }
+        });
+    }
+
+    Ok(())
+}
+
+fn handle_connection(mut stream: TcpStream, state: &ServerState) -> Result<()> {
+    stream.set_read_timeout(Some(REQUEST_READ_TIMEOUT))?;
+    // Bounded, so a client can't make the server buffer endless headers
+    let mut reader = BufReader::new(stream.try_clone()?.take(MAX_HEAD_BYTES));
+
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default().to_string();
+    let path = parts.next().unwrap_or_default().to_string();
+
+    // Only the body length matters, all other headers are skipped
+    let mut content_length = 0usize;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 || header.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':')
+            && name.trim().eq_ignore_ascii_case("content-length")
+        {
+            content_length = value.trim().parse().context("Invalid Content-Length")?;
+        }
+    }
+
+    let (status, response) = match (method.as_str(), path.as_str()) {
+        _ if content_length > MAX_BODY_BYTES => (
+            "413 Payload Too Large",
+            error_body(&format!(
+                "Request body of {} bytes is above the limit of {} bytes",
+                content_length, MAX_BODY_BYTES
+            )),
+        ),
+        ("POST", "/blast") => {
+            reader.get_mut().set_limit(content_length as u64);
+            let mut body = vec![0u8; content_length];
+            reader.read_exact(&mut body)?;
+            handle_blast(&body, state)
+        }
+        _ => (
+            "404 Not Found",
+            error_body(&format!("No route for {} {}", method, path)),
+        ),
+    };
+
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        response.len(),
+        response
+    )?;
+    stream.flush()?;
+    Ok(())
+}
+
+fn handle_blast(body: &[u8], state: &ServerState) -> (&'static str, String) {
+    let request: BlastRequest = match serde_json::from_slice(body) {
+        Ok(request) => request,
+        Err(e) => {
+            return (
+                "400 Bad Request",
+                error_body(&format!("Invalid blast request: {}", e)),
+            );
+        }
+    };
+
+    // Only Ctrl-C stops an unbounded run, and it stops the whole server
+    if request.config.send.is_unbounded() {
+        return (
+            "400 Bad Request",
+            error_body("Blast requests need tot_rows or a duration, they can't run unbounded"),
+        );
+    }
+
+    // Bound concurrency so a burst of requests can't overload the database or this host
+    if state.active_blasts.fetch_add(1, Ordering::SeqCst) >= state.max_concurrent_blasts {
+        state.active_blasts.fetch_sub(1, Ordering::SeqCst);
+        return (
+            "429 Too Many Requests",
+            error_body("Too many concurrent blasts, retry later"),
+        );
+    }
+    let _slot = BlastSlot(&state.active_blasts);
+
+    info!("Received blast request for table '{}'", request.table);
+    let report = blasting::blast_table(
+        &request.table,
+        &request.config,
+        &state.connection,
+        &state.options,
+    );
+    if let Some(e) = &report.error {
+        error!("Table '{}' failed: {}", request.table, e);
+    }
+    if let Some(trace) = &state.options.latency_trace
+        && let Err(e) = trace.finish()
+    {
+        warn!("{:#}", e);
+    }
+
+    let summary = TableSummary {
+        table: &request.table,
+        success: report.is_success(),
+        rows_sent: report.rows_sent,
+        elapsed_secs: report.elapsed_secs,
+        error: report.error.clone(),
+    };
+    let status = if report.is_success() {
+        "200 OK"
+    } else {
+        "500 Internal Server Error"
+    };
+    (
+        status,
+        serde_json::to_string(&summary).unwrap_or_else(|e| error_body(&e.to_string())),
+    )
+}
+
+fn error_body(message: &str) -> String {
+    serde_json::json!({ "error": message }).to_string()
+}
@@ -0,0 +1,115 @@
+use std::sync::Mutex;
+
+use serde::Serialize;
+use tracing::info;
+
+/// Running count, nulls, min, max and sum of one column's generated values. Varchar
+/// columns record their length and DoubleArray columns each of their elements.
+#[derive(Debug, Clone, Default)]
+pub struct ValueStats {
+    count: u64,
+    nulls: u64,
+    min: f64,
+    max: f64,
+    sum: f64,
+}
+
+impl ValueStats {
+    pub fn record(&mut self, value: f64) {
+        if self.count == 0 {
+            self.min = value;
+            self.max = value;
+        } else {
+            self.min = self.min.min(value);
+            self.max = self.max.max(value);
+        }
+        self.count += 1;
+        self.sum += value;
+    }
+
+    pub fn record_null(&mut self) {
+        self.nulls += 1;
+    }
+
+    fn merge(&mut self, other: &ValueStats) {
+        if other.count > 0 {
+            if self.count == 0 {
+                self.min = other.min;
+                self.max = other.max;
+            } else {
+                self.min = self.min.min(other.min);
+                self.max = self.max.max(other.max);
+            }
+        }
+        self.count += other.count;
+        self.nulls += other.nulls;
+        self.sum += other.sum;
+    }
+}
+
+/// Summary of a column's generated values, as logged and written to the JSON report
+#[derive(Debug, Clone, Serialize)]
+pub struct ColumnStats {
+    pub column: String,
+    pub count: u64,
+    pub nulls: u64,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    pub mean: Option<f64>,
+}
+
+/// Per-column statistics merged from every sender of a table as each one finishes
+#[derive(Debug)]
+pub struct StatsCollector {
+    columns: Vec<String>,
+    stats: Mutex<Vec<ValueStats>>,
+}
+
+impl StatsCollector {
+    /// `columns` are the table's non-symbol columns, in the senders' field column order
+    pub fn new(columns: Vec<String>) -> Self {
+        let stats = vec![ValueStats::default(); columns.len()];
+        Self {
+            columns,
+            stats: Mutex::new(stats),
+        }
+    }
+
+    pub fn merge(&self, sender_stats: &[ValueStats]) {
+        let mut stats = self.stats.lock().unwrap();
+        for (total, sender) in stats.iter_mut().zip(sender_stats) {
+            total.merge(sender);
+        }
+    }
+
+    /// Summarizes every column, logging one line per column
+    pub fn summarize(&self, table_name: &str) -> Vec<ColumnStats> {
+        let stats = self.stats.lock().unwrap();
+        self.columns
+            .iter()
+            .zip(stats.iter())
+            .map(|(column, stats)| {
+                let generated = stats.count > 0;
+                let summary = ColumnStats {
+                    column: column.clone(),
+                    count: stats.count,
+                    nulls: stats.nulls,
+                    min: generated.then_some(stats.min),
+                    max: generated.then_some(stats.max),
+                    mean: generated.then(|| stats.sum / stats.count as f64),
+                };
+                info!(
+                    "Table '{}' column '{}': {} values, {} nulls, min {:?}, max {:?}, mean {:?}",
+                    table_name,
+                    column,
+                    summary.count,
+                    summary.nulls,
+                    summary.min,
+                    summary.max,
+                    summary.mean
+                );
+                summary
+            })
+            .collect()
+    }
+}
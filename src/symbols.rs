@@ -0,0 +1,90 @@
+use std::fs;
+
+use anyhow::{Context, Result};
+use rand::distr::weighted::WeightedIndex;
+use tracing::info;
+
+/// Symbol values loaded from a file, one `value` or `value,weight` per line
+#[derive(Debug)]
+pub struct SymbolFile {
+    pub values: Vec<String>,
+    /// Sampling weights when the file is weighted, `None` means uniform sampling
+    pub weights: Option<WeightedIndex<f64>>,
+}
+
+impl SymbolFile {
+    pub fn load(path: &str) -> Result<Self> {
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read symbol file '{}'", path))?;
+
+        let mut values = Vec::new();
+        let mut weights = Vec::new();
+        for (line_no, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let (value, weight) = match line.rsplit_once(',') {
+                Some((value, weight)) => {
+                    let weight: f64 = weight.trim().parse().with_context(|| {
+                        format!("Invalid weight on line {} of '{}'", line_no + 1, path)
+                    })?;
+                    if !weight.is_finite() || weight < 0.0 {
+                        return Err(anyhow::anyhow!(
+                            "Weight on line {} of '{}' must be a non-negative number",
+                            line_no + 1,
+                            path
+                        ));
+                    }
+                    (value.trim(), Some(weight))
+                }
+                None => (line, None),
+            };
+
+            if value.is_empty() || value.chars().any(char::is_control) {
+                return Err(anyhow::anyhow!(
+                    "Invalid symbol value on line {} of '{}'",
+                    line_no + 1,
+                    path
+                ));
+            }
+            // The first line decides whether the file is weighted
+            if !values.is_empty() && weight.is_some() == weights.is_empty() {
+                return Err(anyhow::anyhow!(
+                    "Symbol file '{}' mixes weighted and unweighted lines (line {})",
+                    path,
+                    line_no + 1
+                ));
+            }
+
+            values.push(value.to_string());
+            weights.extend(weight);
+        }
+
+        if values.is_empty() {
+            return Err(anyhow::anyhow!("Symbol file '{}' has no values", path));
+        }
+
+        let weights = if weights.is_empty() {
+            None
+        } else {
+            Some(
+                WeightedIndex::new(&weights)
+                    .with_context(|| format!("Invalid weights in symbol file '{}'", path))?,
+            )
+        };
+
+        info!(
+            "Loaded {} {} symbol values from '{}'",
+            values.len(),
+            if weights.is_some() {
+                "weighted"
+            } else {
+                "uniform"
+            },
+            path
+        );
+        Ok(Self { values, weights })
+    }
+}
@@ -0,0 +1,62 @@
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    sync::Mutex,
+    time::Duration,
+};
+
+use anyhow::{Context, Result};
+use tracing::warn;
+
+/// Buffered capacity for the trace writer, large enough that flush records rarely hit disk
+const TRACE_BUFFER_SIZE: usize = 1 << 20;
+
+/// CSV trace with one record per ILP flush, shared by all senders of all tables
+pub struct LatencyTrace {
+    writer: Mutex<BufWriter<File>>,
+}
+
+impl LatencyTrace {
+    pub fn create(path: &str) -> Result<Self> {
+        let file = File::create(path)
+            .with_context(|| format!("Failed to create latency trace file '{}'", path))?;
+        let mut writer = BufWriter::with_capacity(TRACE_BUFFER_SIZE, file);
+        writeln!(writer, "timestamp_ns,table,sender_id,batch_size,latency_ns")
+            .context("Failed to write latency trace header")?;
+        Ok(Self {
+            writer: Mutex::new(writer),
+        })
+    }
+
+    /// Appends a flush record. Called after the flush returns, so it never
+    /// contributes to the measured latency.
+    pub fn record(
+        &self,
+        timestamp_ns: i64,
+        table_name: &str,
+        sender_id: u16,
+        batch_size: u32,
+        latency: Duration,
+    ) {
+        let mut writer = self.writer.lock().unwrap();
+        if let Err(e) = writeln!(
+            writer,
+            "{},{},{},{},{}",
+            timestamp_ns,
+            table_name,
+            sender_id,
+            batch_size,
+            latency.as_nanos()
+        ) {
+            warn!("Failed to write latency trace record: {}", e);
+        }
+    }
+
+    pub fn finish(&self) -> Result<()> {
+        self.writer
+            .lock()
+            .unwrap()
+            .flush()
+            .context("Failed to flush latency trace file")
+    }
+}
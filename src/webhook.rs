@@ -0,0 +1,41 @@
+use serde::Serialize;
+use tracing::{info, warn};
+
+/// Outcome of blasting a single table, posted when the table completes
+#[derive(Debug, Serialize)]
+pub struct TableSummary<'a> {
+    pub table: &'a str,
+    pub success: bool,
+    pub rows_sent: u64,
+    pub elapsed_secs: f64,
+    pub error: Option<String>,
+}
+
+/// Outcome of the whole run, posted once all tables have completed
+#[derive(Debug, Serialize)]
+pub struct RunSummary {
+    pub success: bool,
+    pub tables: usize,
+    pub failed_tables: usize,
+    pub elapsed_secs: f64,
+    pub errors: Vec<String>,
+}
+
+/// POSTs the summary as JSON. Failures are logged and never fail the run.
+pub fn notify<T: Serialize>(url: &str, summary: &T) {
+    let body = match serde_json::to_string(summary) {
+        Ok(body) => body,
+        Err(e) => {
+            warn!("Failed to serialize webhook payload: {}", e);
+            return;
+        }
+    };
+
+    match ureq::post(url)
+        .header("Content-Type", "application/json")
+        .send(body.as_str())
+    {
+        Ok(_) => info!("Posted completion summary to webhook '{}'", url),
+        Err(e) => warn!("Failed to post completion summary to '{}': {}", url, e),
+    }
+}